@@ -6,5 +6,9 @@ set -ex
 export RUSTFLAGS="-D warnings"
 
 cargo check --no-default-features
+cargo clippy --no-default-features --all-targets
+cargo test --no-default-features
+rustup target add thumbv7em-none-eabi
+cargo check --no-default-features --target thumbv7em-none-eabi
 cargo check --bins --examples --tests
 cargo test --all-features
\ No newline at end of file
@@ -1,12 +1,17 @@
-use std::marker::PhantomData;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "ed25519")]
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+use core::marker::PhantomData;
 
 use generic_array::{arr, typenum, ArrayLength, GenericArray};
 use hmac::crypto_mac::{Mac, MacResult};
 use hmac::digest::{BlockInput, FixedOutput, Input, Reset};
 use hmac::Hmac;
-use typenum::Unsigned;
+use typenum::{IsGreaterOrEqual, IsLessOrEqual, True, Unsigned};
 
-use crate::base64::{self, URLSafeBase64Encode};
+use crate::encoder::SignatureEncoder;
 
 /// A trait which implements signature generation functionality.
 pub trait SigningAlgorithm {
@@ -24,8 +29,36 @@ pub trait SigningAlgorithm {
     }
 }
 
+/// A marker trait for signing algorithms that sign and verify values with the same secret key,
+/// such as [`HMACAlgorithm`], which accept keys of any length. Only these algorithms can be
+/// used with a [`SignerBuilder`], which derives keys from secrets.
+///
+/// Asymmetric algorithms need a valid private key instead, so they are built using an
+/// [`AsymmetricSignerBuilder`]:
+/// ```rust,compile_fail
+/// # #[cfg(feature = "ed25519")] {
+/// use itsdangerous::algorithm::Ed25519Algorithm;
+/// use itsdangerous::key_derivation::DjangoConcat;
+/// use itsdangerous::SignerBuilder;
+///
+/// let signer = SignerBuilder::<sha1::Sha1, Ed25519Algorithm, DjangoConcat>::new("k").build();
+/// # }
+/// # #[cfg(not(feature = "ed25519"))]
+/// # compile_error!("requires the `ed25519` feature");
+/// ```
+///
+/// [`SignerBuilder`]: crate::SignerBuilder
+/// [`AsymmetricSignerBuilder`]: crate::AsymmetricSignerBuilder
+pub trait SymmetricAlgorithm: SigningAlgorithm {}
+
 /// A trait which implements a Signer, which you can append
 /// inputs to, and then generate a final signature with.
+///
+/// The signers built by a [`SignerBuilder`] create a signer once per key, and clone it for
+/// each signature, so signers that are used with them must implement `Clone`. Any expensive
+/// key setup should happen in [`Signer::new`], and `clone` should be cheap.
+///
+/// [`SignerBuilder`]: crate::SignerBuilder
 pub trait Signer: Sized {
     type OutputSize: ArrayLength<u8>;
 
@@ -35,12 +68,51 @@ pub trait Signer: Sized {
 
     fn sign(self) -> Signature<Self::OutputSize>;
 
+    /// Verifies that `signature` is a valid signature of the inputs.
+    ///
+    /// By default, this computes the signature of the inputs and compares it to `signature`
+    /// in constant time.
+    fn verify(self, signature: &Signature<Self::OutputSize>) -> bool {
+        self.sign() == *signature
+    }
+
     fn input_chained(mut self, value: &[u8]) -> Self {
         self.input(value);
         self
     }
 }
 
+/// A trait which implements a signing algorithm that uses a private key to sign values,
+/// where signatures can be verified using only the corresponding public key.
+///
+/// The private key is passed to [`Signer::new`] as-is, so it should be a randomly generated
+/// key of [`PrivateKeySize`] bytes rather than a password.
+///
+/// [`PrivateKeySize`]: AsymmetricAlgorithm::PrivateKeySize
+pub trait AsymmetricAlgorithm: SigningAlgorithm {
+    type PrivateKeySize: ArrayLength<u8>;
+    type PublicKey: Clone;
+
+    /// Returns the public key for a given private key, or `None` if the private key is invalid.
+    fn public_key(private_key: &[u8]) -> Option<Self::PublicKey>;
+
+    /// Decodes a public key that has been encoded using [`encode_public_key`], returning
+    /// `None` if it is invalid.
+    ///
+    /// [`encode_public_key`]: AsymmetricAlgorithm::encode_public_key
+    fn decode_public_key(public_key: &[u8]) -> Option<Self::PublicKey>;
+
+    /// Encodes a public key to bytes.
+    fn encode_public_key(public_key: &Self::PublicKey) -> Vec<u8>;
+
+    /// Verifies that `signature` is a valid signature of `value` for the given public key.
+    fn verify(
+        public_key: &Self::PublicKey,
+        value: &[u8],
+        signature: &Signature<Self::OutputSize>,
+    ) -> bool;
+}
+
 /// Provides an algorithm that does not perform any signing and
 /// returns an empty signature.
 pub struct NoneAlgorithm;
@@ -50,7 +122,10 @@ impl SigningAlgorithm for NoneAlgorithm {
     type Signer = NoneSigner;
 }
 
+impl SymmetricAlgorithm for NoneAlgorithm {}
+
 #[doc(hidden)]
+#[derive(Clone)]
 pub struct NoneSigner;
 impl Signer for NoneSigner {
     type OutputSize = typenum::U0;
@@ -84,7 +159,16 @@ where
     type Signer = HMACSigner<Digest>;
 }
 
+impl<Digest> SymmetricAlgorithm for HMACAlgorithm<Digest>
+where
+    Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
+    Digest::BlockSize: ArrayLength<u8> + Clone,
+    Digest::OutputSize: ArrayLength<u8>,
+{
+}
+
 #[doc(hidden)]
+#[derive(Clone)]
 pub struct HMACSigner<Digest>(Hmac<Digest>)
 where
     Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
@@ -115,6 +199,294 @@ where
     }
 }
 
+/// The smallest size, in bytes, that a [`TruncatedAlgorithm`] can truncate signatures to.
+///
+/// This is 80 bits, which is the lower bound recommended by RFC 2104.
+pub type MinTruncatedSize = typenum::U10;
+
+/// Provides an algorithm that truncates the signatures of another algorithm to their first
+/// `N` bytes, which makes signed values shorter at the cost of security. This is usually
+/// constructed using [`SignerBuilder::with_truncated_signature`].
+///
+/// `N` must be at least [`MinTruncatedSize`], and at most the output size of `Algorithm`,
+/// which is checked at compile time:
+/// ```rust,compile_fail
+/// use itsdangerous::default_builder;
+/// use itsdangerous::typenum::U4;
+///
+/// let signer = default_builder("secret key")
+///     .with_truncated_signature::<U4>()
+///     .build();
+/// ```
+///
+/// Truncation is only meaningful for MAC algorithms, such as [`HMACAlgorithm`].
+///
+/// [`SignerBuilder::with_truncated_signature`]: crate::SignerBuilder::with_truncated_signature
+pub struct TruncatedAlgorithm<Algorithm, N>(PhantomData<(Algorithm, N)>);
+
+impl<Algorithm, N> SigningAlgorithm for TruncatedAlgorithm<Algorithm, N>
+where
+    Algorithm: SigningAlgorithm,
+    N: ArrayLength<u8>
+        + Unsigned
+        + IsGreaterOrEqual<MinTruncatedSize, Output = True>
+        + IsLessOrEqual<Algorithm::OutputSize, Output = True>,
+{
+    type OutputSize = N;
+    type Signer = TruncatedSigner<Algorithm::Signer, N>;
+}
+
+impl<Algorithm, N> SymmetricAlgorithm for TruncatedAlgorithm<Algorithm, N>
+where
+    Algorithm: SymmetricAlgorithm,
+    N: ArrayLength<u8>
+        + Unsigned
+        + IsGreaterOrEqual<MinTruncatedSize, Output = True>
+        + IsLessOrEqual<Algorithm::OutputSize, Output = True>,
+{
+}
+
+#[doc(hidden)]
+pub struct TruncatedSigner<TSigner, N>(TSigner, PhantomData<N>);
+
+impl<TSigner: Clone, N> Clone for TruncatedSigner<TSigner, N> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<TSigner, N> Signer for TruncatedSigner<TSigner, N>
+where
+    TSigner: Signer,
+    N: ArrayLength<u8> + IsLessOrEqual<TSigner::OutputSize, Output = True>,
+{
+    type OutputSize = N;
+
+    fn new(key: &[u8]) -> Self {
+        Self(TSigner::new(key), PhantomData)
+    }
+
+    #[inline(always)]
+    fn input(&mut self, value: &[u8]) {
+        self.0.input(value)
+    }
+
+    /// Signs the inputs and truncates the signature. The default `verify` then compares
+    /// only the truncated signature, which is still done in constant time.
+    #[inline(always)]
+    fn sign(self) -> Signature<Self::OutputSize> {
+        let signature = self.0.sign().code();
+        GenericArray::clone_from_slice(&signature[..N::USIZE]).into()
+    }
+}
+
+/// Provides an algorithm that does signature generation using Ed25519ph, as specified by
+/// RFC 8032, without a context.
+///
+/// Values are hashed using SHA-512 as they are input, and the hash is signed, so that values
+/// can be signed in chunks without buffering them. Signatures are not compatible with plain
+/// Ed25519.
+///
+/// The private key is a 32 byte seed, and the public key is encoded as 32 bytes.
+#[cfg(feature = "ed25519")]
+pub struct Ed25519Algorithm;
+
+#[cfg(feature = "ed25519")]
+impl SigningAlgorithm for Ed25519Algorithm {
+    type OutputSize = typenum::U64;
+    type Signer = Ed25519Signer;
+}
+
+#[cfg(feature = "ed25519")]
+impl AsymmetricAlgorithm for Ed25519Algorithm {
+    type PrivateKeySize = typenum::U32;
+    type PublicKey = ed25519_dalek::VerifyingKey;
+
+    fn public_key(private_key: &[u8]) -> Option<Self::PublicKey> {
+        let private_key = private_key.try_into().ok()?;
+        Some(ed25519_dalek::SigningKey::from_bytes(private_key).verifying_key())
+    }
+
+    fn decode_public_key(public_key: &[u8]) -> Option<Self::PublicKey> {
+        ed25519_dalek::VerifyingKey::try_from(public_key).ok()
+    }
+
+    fn encode_public_key(public_key: &Self::PublicKey) -> Vec<u8> {
+        public_key.to_bytes().to_vec()
+    }
+
+    fn verify(
+        public_key: &Self::PublicKey,
+        value: &[u8],
+        signature: &Signature<Self::OutputSize>,
+    ) -> bool {
+        use ed25519_dalek::Digest;
+        let digest = ed25519_dalek::Sha512::new().chain_update(value);
+        verify_ed25519_prehashed(public_key, digest, signature)
+    }
+}
+
+#[cfg(feature = "ed25519")]
+fn verify_ed25519_prehashed(
+    public_key: &ed25519_dalek::VerifyingKey,
+    digest: ed25519_dalek::Sha512,
+    signature: &Signature<typenum::U64>,
+) -> bool {
+    match ed25519_dalek::Signature::from_slice(&signature.to_bytes()) {
+        Ok(signature) => public_key
+            .verify_prehashed_strict(digest, None, &signature)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+#[cfg(feature = "ed25519")]
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct Ed25519Signer {
+    key: ed25519_dalek::SigningKey,
+    digest: ed25519_dalek::Sha512,
+}
+
+#[cfg(feature = "ed25519")]
+impl Signer for Ed25519Signer {
+    type OutputSize = typenum::U64;
+
+    fn new(key: &[u8]) -> Self {
+        let key = key.try_into().expect("ed25519 private keys are 32 bytes");
+        Self {
+            key: ed25519_dalek::SigningKey::from_bytes(key),
+            digest: Default::default(),
+        }
+    }
+
+    #[inline(always)]
+    fn input(&mut self, value: &[u8]) {
+        ed25519_dalek::Digest::update(&mut self.digest, value)
+    }
+
+    #[inline(always)]
+    fn sign(self) -> Signature<Self::OutputSize> {
+        // Signing only fails if the context is longer than 255 bytes.
+        let signature = self.key.sign_prehashed(self.digest, None).unwrap();
+        GenericArray::clone_from_slice(&signature.to_bytes()).into()
+    }
+
+    fn verify(self, signature: &Signature<Self::OutputSize>) -> bool {
+        verify_ed25519_prehashed(&self.key.verifying_key(), self.digest, signature)
+    }
+}
+
+/// Provides an algorithm that does signature generation using ECDSA with the NIST P-256
+/// curve and SHA-256.
+///
+/// The private key is a 32 byte scalar, and the public key is encoded as a 33 byte
+/// compressed SEC1 point. Signatures are deterministic, as specified by RFC 6979, and are
+/// normalized to a low S, so that each value has a single valid signature. Signatures with a
+/// high S are rejected.
+///
+/// Values are hashed using SHA-256 as they are input, so that values can be signed in chunks
+/// without buffering them.
+#[cfg(feature = "p256")]
+pub struct EcdsaP256Algorithm;
+
+#[cfg(feature = "p256")]
+impl SigningAlgorithm for EcdsaP256Algorithm {
+    type OutputSize = typenum::U64;
+    type Signer = EcdsaP256Signer;
+}
+
+#[cfg(feature = "p256")]
+impl AsymmetricAlgorithm for EcdsaP256Algorithm {
+    type PrivateKeySize = typenum::U32;
+    type PublicKey = p256::ecdsa::VerifyingKey;
+
+    fn public_key(private_key: &[u8]) -> Option<Self::PublicKey> {
+        let private_key = p256::ecdsa::SigningKey::from_slice(private_key).ok()?;
+        Some(*private_key.verifying_key())
+    }
+
+    fn decode_public_key(public_key: &[u8]) -> Option<Self::PublicKey> {
+        p256::ecdsa::VerifyingKey::from_sec1_bytes(public_key).ok()
+    }
+
+    fn encode_public_key(public_key: &Self::PublicKey) -> Vec<u8> {
+        public_key.to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    fn verify(
+        public_key: &Self::PublicKey,
+        value: &[u8],
+        signature: &Signature<Self::OutputSize>,
+    ) -> bool {
+        use p256::ecdsa::signature::Verifier;
+        match decode_p256_signature(signature) {
+            Some(signature) => public_key.verify(value, &signature).is_ok(),
+            None => false,
+        }
+    }
+}
+
+/// Decodes a P-256 signature, unless its S is high. For every signature (r, s), (r, n - s) is
+/// also valid, so only accepting the low one makes signatures non-malleable.
+#[cfg(feature = "p256")]
+fn decode_p256_signature(signature: &Signature<typenum::U64>) -> Option<p256::ecdsa::Signature> {
+    let signature = p256::ecdsa::Signature::from_slice(&signature.to_bytes()).ok()?;
+    match signature.normalize_s() {
+        Some(_) => None,
+        None => Some(signature),
+    }
+}
+
+#[cfg(feature = "p256")]
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct EcdsaP256Signer {
+    key: p256::ecdsa::SigningKey,
+    digest: sha2::Sha256,
+}
+
+#[cfg(feature = "p256")]
+impl Signer for EcdsaP256Signer {
+    type OutputSize = typenum::U64;
+
+    fn new(key: &[u8]) -> Self {
+        Self {
+            key: p256::ecdsa::SigningKey::from_slice(key).expect("invalid p-256 private key"),
+            digest: Default::default(),
+        }
+    }
+
+    #[inline(always)]
+    fn input(&mut self, value: &[u8]) {
+        self.digest.input(value)
+    }
+
+    /// Signs the SHA-256 hash of the inputs, which gives the same signature as signing the
+    /// inputs all at once.
+    #[inline(always)]
+    fn sign(self) -> Signature<Self::OutputSize> {
+        use p256::ecdsa::signature::hazmat::PrehashSigner;
+        // Like `signature::Signer::sign`, this only fails if the hash has the wrong size.
+        let signature: p256::ecdsa::Signature =
+            self.key.sign_prehash(&self.digest.fixed_result()).unwrap();
+        let signature = signature.normalize_s().unwrap_or(signature);
+        GenericArray::clone_from_slice(&signature.to_bytes()).into()
+    }
+
+    fn verify(self, signature: &Signature<Self::OutputSize>) -> bool {
+        use p256::ecdsa::signature::hazmat::PrehashVerifier;
+        match decode_p256_signature(signature) {
+            Some(signature) => self
+                .key
+                .verifying_key()
+                .verify_prehash(&self.digest.fixed_result(), &signature)
+                .is_ok(),
+            None => false,
+        }
+    }
+}
+
 /// Represents a computed signature.
 ///
 /// Two signatures of the same type can be compared safely using Eq/PartialEq,
@@ -127,11 +499,48 @@ impl<N: ArrayLength<u8>> Signature<N> {
     fn code(self) -> GenericArray<u8, N> {
         self.0.code()
     }
+
+    #[cfg(any(feature = "ed25519", feature = "p256"))]
+    #[inline(always)]
+    pub(crate) fn to_bytes(&self) -> GenericArray<u8, N> {
+        self.0.clone().code()
+    }
 }
 
-impl<N: ArrayLength<u8>> URLSafeBase64Encode for Signature<N> {
-    fn base64_encode_str(self, target: &mut String) {
-        base64::encode_str(self.code().as_slice(), target)
+impl<N: ArrayLength<u8>> Signature<N> {
+    /// Encodes the signature using `Encoder`, appending it to `target`.
+    #[inline(always)]
+    pub(crate) fn encode_str<Encoder: SignatureEncoder>(self, target: &mut String) {
+        Encoder::encode_str(self.code().as_slice(), target)
+    }
+
+    /// Encodes the signature using `Encoder`, appending it to `target`.
+    #[inline(always)]
+    pub(crate) fn encode_vec<Encoder: SignatureEncoder>(self, target: &mut Vec<u8>) {
+        Encoder::encode_vec(self.code().as_slice(), target)
+    }
+
+    /// Encodes the signature using `Encoder` into the start of `target`, returning the
+    /// number of bytes written.
+    #[inline(always)]
+    pub(crate) fn encode_slice<Encoder: SignatureEncoder>(self, target: &mut [u8]) -> usize {
+        Encoder::encode_slice(self.code().as_slice(), target)
+    }
+
+    /// Encodes the signature using `Encoder`, writing it to `target`.
+    #[inline(always)]
+    pub(crate) fn encode_fmt<Encoder, W>(self, target: &mut W) -> fmt::Result
+    where
+        Encoder: SignatureEncoder,
+        W: ?Sized + fmt::Write,
+    {
+        Encoder::encode_fmt(self.code().as_slice(), target)
+    }
+
+    /// Decodes a signature that was encoded using `Encoder`.
+    #[inline(always)]
+    pub(crate) fn decode<Encoder: SignatureEncoder>(encoded_signature: &[u8]) -> Option<Self> {
+        Encoder::decode(encoded_signature).map(Self::from)
     }
 }
 
@@ -156,7 +565,14 @@ impl<N: ArrayLength<u8>> From<GenericArray<u8, N>> for Signature<N> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::encoder::Base64;
     use sha1::Sha1;
+
+    fn base64_encode<N: ArrayLength<u8>>(signature: Signature<N>) -> String {
+        let mut output = String::new();
+        signature.encode_str::<Base64>(&mut output);
+        output
+    }
     #[test]
     fn test_hmac_algorithm() {
         type Algorithm = HMACAlgorithm<Sha1>;
@@ -165,7 +581,22 @@ mod test {
 
         assert!(signature == signature2);
         // This is tested against Python's `HMACAlgorithm` implementation.
-        assert_eq!(signature.base64_encode(), "RrTsWGEXFU2s1J1mTl1j_ciO-1E");
+        assert_eq!(base64_encode(signature), "RrTsWGEXFU2s1J1mTl1j_ciO-1E");
+    }
+
+    #[test]
+    fn test_truncated_algorithm() {
+        type Algorithm = TruncatedAlgorithm<HMACAlgorithm<Sha1>, typenum::U10>;
+        let signature = Algorithm::get_signature(b"foo", b"bar");
+        assert_eq!(base64_encode(signature), "RrTsWGEXFU2s1A");
+
+        let signature = Algorithm::get_signature(b"foo", b"bar");
+        assert!(Algorithm::get_signer(b"foo")
+            .input_chained(b"bar")
+            .verify(&signature));
+        assert!(!Algorithm::get_signer(b"foo")
+            .input_chained(b"baz")
+            .verify(&signature));
     }
 
     #[test]
@@ -175,6 +606,6 @@ mod test {
         let signature2 = Algorithm::get_signer(b"foo").input_chained(b"bar").sign();
 
         assert!(signature == signature2);
-        assert_eq!(signature.base64_encode(), "");
+        assert_eq!(base64_encode(signature), "");
     }
 }
@@ -0,0 +1,657 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::{any, fmt, mem};
+
+use generic_array::GenericArray;
+use typenum::Unsigned;
+use zeroize::Zeroize;
+
+use crate::algorithm::{AsymmetricAlgorithm, Signature};
+use crate::encoder::{self, Base64};
+use crate::error::InvalidKey;
+use crate::secret::{RedactedKeys, SecretKey};
+use crate::signer::SignerImpl;
+use crate::timed::TimestampVerifierImpl;
+use crate::traits::GetEncoder;
+use crate::{IntoTimestampVerifier, Separator, Verifier};
+
+/// A builder for a [`Signer`] that signs values with the private key of an asymmetric
+/// signing algorithm, such as [`Ed25519Algorithm`].
+///
+/// Signed values use the same format as those of a [`SignerBuilder`], and can be unsigned
+/// either by the resulting signer, or by a [`VerifierImpl`] built from the public key.
+///
+/// # Remarks
+/// Private keys are used as-is, without any key derivation, so there is no salt to
+/// namespace signatures with. Use a separate key pair for each purpose instead.
+///
+/// [`Signer`]: crate::Signer
+/// [`SignerBuilder`]: crate::SignerBuilder
+/// [`Ed25519Algorithm`]: crate::algorithm::Ed25519Algorithm
+pub struct AsymmetricSignerBuilder<Algorithm, Encoder = Base64> {
+    /// Private keys, ordered from oldest to newest. The newest key is used for signing.
+    private_keys: Vec<Vec<u8>>,
+    separator: Separator,
+    _phantom: PhantomData<(Algorithm, Encoder)>,
+}
+
+/// A builder for a [`VerifierImpl`], which can unsign values signed by an asymmetric
+/// signing algorithm using only the public key.
+pub struct VerifierBuilder<Algorithm, Encoder = Base64> {
+    /// Public keys, ordered from oldest to newest.
+    public_keys: Vec<Vec<u8>>,
+    separator: Separator,
+    _phantom: PhantomData<(Algorithm, Encoder)>,
+}
+
+/// Constructs a signer builder using [`Ed25519`] with the given 32 byte private key.
+/// Requires the `ed25519` feature.
+///
+/// [`Ed25519`]: crate::algorithm::Ed25519Algorithm
+#[cfg(feature = "ed25519")]
+pub fn ed25519_builder<K: AsRef<[u8]>>(
+    private_key: K,
+) -> AsymmetricSignerBuilder<crate::algorithm::Ed25519Algorithm> {
+    AsymmetricSignerBuilder::new(private_key)
+}
+
+/// Constructs a verifier builder using [`Ed25519`] with the given 32 byte public key.
+/// Requires the `ed25519` feature.
+///
+/// [`Ed25519`]: crate::algorithm::Ed25519Algorithm
+#[cfg(feature = "ed25519")]
+pub fn ed25519_verifier_builder<K: AsRef<[u8]>>(
+    public_key: K,
+) -> VerifierBuilder<crate::algorithm::Ed25519Algorithm> {
+    VerifierBuilder::new(public_key)
+}
+
+/// Constructs a signer builder using [`ECDSA P-256`] with the given 32 byte private key.
+/// Requires the `p256` feature.
+///
+/// [`ECDSA P-256`]: crate::algorithm::EcdsaP256Algorithm
+#[cfg(feature = "p256")]
+pub fn ecdsa_p256_builder<K: AsRef<[u8]>>(
+    private_key: K,
+) -> AsymmetricSignerBuilder<crate::algorithm::EcdsaP256Algorithm> {
+    AsymmetricSignerBuilder::new(private_key)
+}
+
+/// Constructs a verifier builder using [`ECDSA P-256`] with the given SEC1 encoded
+/// public key. Requires the `p256` feature.
+///
+/// [`ECDSA P-256`]: crate::algorithm::EcdsaP256Algorithm
+#[cfg(feature = "p256")]
+pub fn ecdsa_p256_verifier_builder<K: AsRef<[u8]>>(
+    public_key: K,
+) -> VerifierBuilder<crate::algorithm::EcdsaP256Algorithm> {
+    VerifierBuilder::new(public_key)
+}
+
+impl<Algorithm> AsymmetricSignerBuilder<Algorithm>
+where
+    Algorithm: AsymmetricAlgorithm,
+{
+    /// Constructs a new signer builder with a given private key.
+    pub fn new<K: AsRef<[u8]>>(private_key: K) -> Self {
+        Self::with_private_keys(Some(private_key))
+    }
+
+    /// Constructs a new signer builder with a list of private keys, ordered from
+    /// oldest to newest.
+    ///
+    /// The newest key is used to sign values. When unsigning, a signature created
+    /// by any of the keys is accepted, with the newest key being attempted first.
+    ///
+    /// # Panics
+    /// Panics if `private_keys` is empty.
+    pub fn with_private_keys<I, K>(private_keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<[u8]>,
+    {
+        let private_keys: Vec<_> = private_keys
+            .into_iter()
+            .map(|key| key.as_ref().to_vec())
+            .collect();
+        assert!(
+            !private_keys.is_empty(),
+            "at least one private key must be provided"
+        );
+
+        Self {
+            private_keys,
+            separator: Default::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Algorithm, Encoder> AsymmetricSignerBuilder<Algorithm, Encoder>
+where
+    Algorithm: AsymmetricAlgorithm,
+    Encoder: encoder::SignatureEncoder,
+{
+    /// Uses a specific separator with the signer. If no separator is
+    /// defined, will default to '.'
+    pub fn with_separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Uses a specific encoder for signatures and timestamps, see
+    /// [`SignerBuilder::with_encoder`]. Verifiers must use the same encoder.
+    ///
+    /// [`SignerBuilder::with_encoder`]: crate::SignerBuilder::with_encoder
+    pub fn with_encoder<E: encoder::SignatureEncoder>(
+        mut self,
+        _encoder: E,
+    ) -> AsymmetricSignerBuilder<Algorithm, E> {
+        AsymmetricSignerBuilder {
+            private_keys: mem::take(&mut self.private_keys),
+            separator: mem::take(&mut self.separator),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Builds a Signer using the configuration specified in this builder, or returns
+    /// an error if any of the private keys are invalid.
+    ///
+    /// # Panics
+    /// Panics if the separator is in the alphabet of the encoder, or of its timestamps.
+    pub fn build(
+        mut self,
+    ) -> Result<SignerImpl<Algorithm, Algorithm::PrivateKeySize, Encoder>, InvalidKey> {
+        assert!(
+            self.separator.is_valid_for::<Encoder>(),
+            "the separator must not be in the alphabet of the encoder or of its timestamps"
+        );
+        let mut private_keys = Vec::with_capacity(self.private_keys.len());
+        for (index, private_key) in self.private_keys.iter().enumerate().rev() {
+            if private_key.len() != Algorithm::PrivateKeySize::USIZE
+                || Algorithm::public_key(private_key).is_none()
+            {
+                return Err(InvalidKey::PrivateKey(index));
+            }
+            private_keys.push(SecretKey::new(GenericArray::clone_from_slice(private_key)));
+        }
+
+        let mut private_keys = private_keys.into_iter();
+        // The constructors guarantee that there is at least one private key.
+        let private_key = private_keys.next().unwrap();
+        Ok(SignerImpl::with_derived_keys(
+            private_key,
+            private_keys.collect(),
+            None,
+            mem::take(&mut self.separator),
+        ))
+    }
+}
+
+impl<Algorithm, Encoder> Drop for AsymmetricSignerBuilder<Algorithm, Encoder> {
+    fn drop(&mut self) {
+        self.private_keys.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl<Algorithm, Encoder> fmt::Debug for AsymmetricSignerBuilder<Algorithm, Encoder> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsymmetricSignerBuilder")
+            .field("algorithm", &any::type_name::<Algorithm>())
+            .field("encoder", &any::type_name::<Encoder>())
+            .field("private_keys", &RedactedKeys(self.private_keys.len()))
+            .field("separator", &self.separator)
+            .finish()
+    }
+}
+
+impl<Algorithm, SignatureEncoder> SignerImpl<Algorithm, Algorithm::PrivateKeySize, SignatureEncoder>
+where
+    Algorithm: AsymmetricAlgorithm,
+    SignatureEncoder: encoder::SignatureEncoder,
+{
+    /// Returns the encoded public key that corresponds to the private key used for signing.
+    pub fn public_key(&self) -> Vec<u8> {
+        // Private keys were validated by the builder.
+        let public_key = Algorithm::public_key(self.derived_keys().next().unwrap()).unwrap();
+        Algorithm::encode_public_key(&public_key)
+    }
+
+    /// Returns a verifier that accepts the same signatures as this signer, without
+    /// being able to sign values itself.
+    pub fn verifier(&self) -> VerifierImpl<Algorithm, SignatureEncoder> {
+        let mut public_keys = self
+            .derived_keys()
+            .map(|private_key| Algorithm::public_key(private_key).unwrap());
+        VerifierImpl {
+            public_key: public_keys.next().unwrap(),
+            fallback_public_keys: public_keys.collect(),
+            separator: self.separator.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Algorithm> VerifierBuilder<Algorithm>
+where
+    Algorithm: AsymmetricAlgorithm,
+{
+    /// Constructs a new verifier builder with a given encoded public key.
+    pub fn new<K: AsRef<[u8]>>(public_key: K) -> Self {
+        Self::with_public_keys(Some(public_key))
+    }
+
+    /// Constructs a new verifier builder with a list of encoded public keys, ordered from
+    /// oldest to newest. A signature created by any of the corresponding private keys
+    /// is accepted, with the newest key being attempted first.
+    ///
+    /// # Panics
+    /// Panics if `public_keys` is empty.
+    pub fn with_public_keys<I, K>(public_keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<[u8]>,
+    {
+        let public_keys: Vec<_> = public_keys
+            .into_iter()
+            .map(|key| key.as_ref().to_vec())
+            .collect();
+        assert!(
+            !public_keys.is_empty(),
+            "at least one public key must be provided"
+        );
+
+        Self {
+            public_keys,
+            separator: Default::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Algorithm, Encoder> VerifierBuilder<Algorithm, Encoder>
+where
+    Algorithm: AsymmetricAlgorithm,
+    Encoder: encoder::SignatureEncoder,
+{
+    /// Uses a specific separator with the verifier. If no separator is
+    /// defined, will default to '.'
+    pub fn with_separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Uses a specific encoder for signatures and timestamps, which must be the one that
+    /// the signer uses, see [`AsymmetricSignerBuilder::with_encoder`].
+    pub fn with_encoder<E: encoder::SignatureEncoder>(
+        self,
+        _encoder: E,
+    ) -> VerifierBuilder<Algorithm, E> {
+        VerifierBuilder {
+            public_keys: self.public_keys,
+            separator: self.separator,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Builds a Verifier using the configuration specified in this builder, or returns
+    /// an error if any of the public keys are invalid.
+    ///
+    /// # Panics
+    /// Panics if the separator is in the alphabet of the encoder, or of its timestamps.
+    pub fn build(self) -> Result<VerifierImpl<Algorithm, Encoder>, InvalidKey> {
+        assert!(
+            self.separator.is_valid_for::<Encoder>(),
+            "the separator must not be in the alphabet of the encoder or of its timestamps"
+        );
+        let mut public_keys = Vec::with_capacity(self.public_keys.len());
+        for (index, public_key) in self.public_keys.iter().enumerate().rev() {
+            match Algorithm::decode_public_key(public_key) {
+                Some(public_key) => public_keys.push(public_key),
+                None => return Err(InvalidKey::PublicKey(index)),
+            }
+        }
+
+        let mut public_keys = public_keys.into_iter();
+        // The constructors guarantee that there is at least one public key.
+        let public_key = public_keys.next().unwrap();
+        Ok(VerifierImpl {
+            public_key,
+            fallback_public_keys: public_keys.collect(),
+            separator: self.separator,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+pub struct VerifierImpl<Algorithm, SignatureEncoder>
+where
+    Algorithm: AsymmetricAlgorithm,
+{
+    public_key: Algorithm::PublicKey,
+    /// Keys that are still accepted when unsigning, ordered from newest to oldest.
+    fallback_public_keys: Vec<Algorithm::PublicKey>,
+    separator: Separator,
+    _phantom: PhantomData<SignatureEncoder>,
+}
+
+impl<Algorithm, SignatureEncoder> fmt::Debug for VerifierImpl<Algorithm, SignatureEncoder>
+where
+    Algorithm: AsymmetricAlgorithm,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VerifierImpl")
+            .field("algorithm", &any::type_name::<Algorithm>())
+            .field("public_keys", &(1 + self.fallback_public_keys.len()))
+            .field("separator", &self.separator)
+            .finish()
+    }
+}
+
+impl<Algorithm, SignatureEncoder> Verifier for VerifierImpl<Algorithm, SignatureEncoder>
+where
+    Algorithm: AsymmetricAlgorithm,
+    SignatureEncoder: encoder::SignatureEncoder,
+{
+    #[inline(always)]
+    fn separator(&self) -> &Separator {
+        &self.separator
+    }
+
+    fn verify_encoded_signature(&self, value: &[u8], encoded_signature: &[u8]) -> bool {
+        let signature = match Signature::decode::<SignatureEncoder>(encoded_signature) {
+            Some(signature) => signature,
+            None => return false,
+        };
+
+        Some(&self.public_key)
+            .into_iter()
+            .chain(&self.fallback_public_keys)
+            .any(|public_key| Algorithm::verify(public_key, value, &signature))
+    }
+}
+
+impl<Algorithm, SignatureEncoder> GetEncoder for VerifierImpl<Algorithm, SignatureEncoder>
+where
+    Algorithm: AsymmetricAlgorithm,
+    SignatureEncoder: encoder::SignatureEncoder,
+{
+    type Encoder = SignatureEncoder;
+}
+
+impl<Algorithm, SignatureEncoder> IntoTimestampVerifier
+    for VerifierImpl<Algorithm, SignatureEncoder>
+where
+    Algorithm: AsymmetricAlgorithm,
+    SignatureEncoder: encoder::SignatureEncoder,
+{
+    type TimestampVerifier = TimestampVerifierImpl<Self>;
+
+    fn into_timestamp_verifier(self) -> Self::TimestampVerifier {
+        TimestampVerifierImpl::with_verifier(self)
+    }
+}
+
+#[cfg(all(test, any(feature = "ed25519", feature = "p256")))]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+    use crate::algorithm;
+    use crate::clock::FixedClock;
+    use crate::encoder::SignatureEncoder;
+    use crate::{IntoTimestampSigner, Signer, StreamingSigner, TimestampSigner, TimestampVerifier};
+
+    fn check_signer_and_verifier<Algorithm>(old_private_key: &[u8], private_key: &[u8])
+    where
+        Algorithm: AsymmetricAlgorithm,
+        Algorithm::Signer: Clone,
+    {
+        let old_signer = AsymmetricSignerBuilder::<Algorithm>::new(old_private_key)
+            .build()
+            .unwrap();
+        let signer = AsymmetricSignerBuilder::<Algorithm>::with_private_keys(vec![
+            old_private_key,
+            private_key,
+        ])
+        .build()
+        .unwrap();
+
+        let signed = signer.sign("hello world");
+        assert!(signed.starts_with("hello world."));
+        assert_eq!(signer.unsign(&signed).unwrap(), "hello world");
+        let signed_with_old_key = old_signer.sign("hello world");
+        assert_eq!(signer.unsign(&signed_with_old_key).unwrap(), "hello world");
+
+        let verifier = VerifierBuilder::<Algorithm>::new(signer.public_key())
+            .build()
+            .unwrap();
+        assert_eq!(verifier.unsign(&signed).unwrap(), "hello world");
+        assert_eq!(
+            verifier.unsign_bytes(signed.as_bytes()).unwrap(),
+            b"hello world"
+        );
+        assert!(verifier.unsign(&signed_with_old_key).is_err());
+        assert!(verifier.unsign(&signed.replace("hello", "howdy")).is_err());
+        assert!(verifier.unsign("hello world").is_err());
+        assert!(verifier.unsign("hello world.bm9wZQ").is_err());
+
+        let verifier = VerifierBuilder::<Algorithm>::with_public_keys(vec![
+            old_signer.public_key(),
+            signer.public_key(),
+        ])
+        .build()
+        .unwrap();
+        assert_eq!(
+            verifier.unsign(&signed_with_old_key).unwrap(),
+            "hello world"
+        );
+        assert_eq!(
+            signer.verifier().unsign(&signed_with_old_key).unwrap(),
+            "hello world"
+        );
+
+        let signature = signer.sign_detached("hello world");
+        assert!(verifier.verify_detached("hello world", &signature).is_ok());
+        assert!(verifier.verify_detached("howdy world", &signature).is_err());
+
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let verifier = verifier.into_timestamp_verifier();
+        let signer = signer.into_timestamp_signer();
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+        let unsigned = verifier.unsign(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.timestamp(), timestamp);
+
+        let (encoded_timestamp, signature) =
+            signer.sign_detached_with_timestamp("hello world", timestamp);
+        let unsigned = verifier
+            .verify_detached("hello world", &encoded_timestamp, &signature)
+            .unwrap();
+        assert_eq!(unsigned.timestamp(), timestamp);
+        assert!(verifier
+            .verify_detached("hello world", "XP57dh", &signature)
+            .is_err());
+
+        let verifier = verifier.with_clock(FixedClock(timestamp));
+        let signed = signer.sign_with_expiry("hello world", timestamp + Duration::from_secs(1));
+        let unsigned = verifier.unsign_with_expiry(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        let signed = signer.sign_with_expiry("hello world", timestamp);
+        assert!(verifier
+            .unsign_with_expiry(&signed)
+            .err()
+            .unwrap()
+            .is_expired());
+
+        // Tokens and values signed with a timestamp cannot be unsigned as each other.
+        let signed = signer.sign_with_validity("hello", timestamp, timestamp);
+        assert!(verifier.unsign(&signed).err().unwrap().is_tampered());
+        let signed = signer.sign_with_timestamp("hello.XP57dg", timestamp + Duration::from_secs(1));
+        assert!(verifier
+            .unsign_with_expiry(&signed)
+            .err()
+            .unwrap()
+            .is_tampered());
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_ed25519() {
+        check_signer_and_verifier::<algorithm::Ed25519Algorithm>(&[1; 32], &[2; 32]);
+
+        // RFC 8032, section 7.3, test abc.
+        let private_key = b"\x83\x3f\xe6\x24\x09\x23\x7b\x9d\x62\xec\x77\x58\x75\x20\x91\x1e\
+              \x9a\x75\x9c\xec\x1d\x19\x75\x5b\x7d\xa9\x01\xb9\x6d\xca\x3d\x42";
+        let signer = ed25519_builder(private_key).build().unwrap();
+        assert_eq!(
+            signer.public_key(),
+            b"\xec\x17\x2b\x93\xad\x5e\x56\x3b\xf4\x93\x2c\x70\xe1\x24\x50\x34\
+              \xc3\x54\x67\xef\x2e\xfd\x4d\x64\xeb\xf8\x19\x68\x34\x67\xe2\xbf"
+        );
+        let signature = b"\x98\xa7\x02\x22\xf0\xb8\x12\x1a\xa9\xd3\x0f\x81\x3d\x68\x3f\x80\
+              \x9e\x46\x2b\x46\x9c\x7f\xf8\x76\x39\x49\x9b\xb9\x4e\x6d\xae\x41\
+              \x31\xf8\x50\x42\x46\x3c\x2a\x35\x5a\x20\x03\xd0\x62\xad\xf5\xaa\
+              \xa1\x0b\x8c\x61\xe6\x36\x06\x2a\xaa\xd1\x1c\x2a\x26\x08\x34\x06";
+        let mut encoded_signature = String::new();
+        Base64::encode_str(signature, &mut encoded_signature);
+        assert_eq!(signer.sign_detached("abc"), encoded_signature);
+
+        // Values are hashed as they are input, rather than buffered.
+        let mut stream = signer.sign_stream();
+        stream.update(b"a");
+        stream.update(b"bc");
+        assert_eq!(stream.finish(), encoded_signature);
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_verifier_with_base64_profile() {
+        use crate::encoder::StandardBase64;
+
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560214527);
+        let signer = ed25519_builder([1; 32])
+            .with_encoder(StandardBase64)
+            .build()
+            .unwrap();
+        let verifier = ed25519_verifier_builder(signer.public_key())
+            .with_encoder(StandardBase64)
+            .build()
+            .unwrap()
+            .into_timestamp_verifier()
+            .with_clock(FixedClock(timestamp));
+        let signer = signer.into_timestamp_signer();
+
+        // The timestamp is not url-safe base64, so it must be decoded using the profile.
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+        assert!(signed.starts_with("hello world.XP77/w==."));
+        let unsigned = verifier.unsign(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.timestamp(), timestamp);
+        let unsigned = verifier.unsign_bytes(signed.as_bytes()).unwrap();
+        assert_eq!(unsigned.timestamp(), timestamp);
+
+        let (encoded_timestamp, signature) =
+            signer.sign_detached_with_timestamp("hello world", timestamp);
+        assert_eq!(encoded_timestamp, "XP77/w==");
+        let unsigned = verifier
+            .verify_detached("hello world", &encoded_timestamp, &signature)
+            .unwrap();
+        assert_eq!(unsigned.timestamp(), timestamp);
+
+        let signed = signer.sign_with_expiry("hello world", timestamp + Duration::from_secs(1));
+        let unsigned = verifier.unsign_with_expiry(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    fn test_ed25519_invalid_keys() {
+        assert_eq!(
+            ed25519_builder([1; 31]).build().err(),
+            Some(InvalidKey::PrivateKey(0))
+        );
+        assert_eq!(
+            AsymmetricSignerBuilder::<algorithm::Ed25519Algorithm>::with_private_keys(vec![
+                &[1; 32][..],
+                &[1; 33][..],
+            ])
+            .build()
+            .err(),
+            Some(InvalidKey::PrivateKey(1))
+        );
+        assert_eq!(
+            ed25519_verifier_builder([1; 31]).build().err(),
+            Some(InvalidKey::PublicKey(0))
+        );
+    }
+
+    #[cfg(feature = "p256")]
+    #[test]
+    fn test_ecdsa_p256() {
+        check_signer_and_verifier::<algorithm::EcdsaP256Algorithm>(&[1; 32], &[2; 32]);
+
+        let signer = ecdsa_p256_builder([3; 32]).build().unwrap();
+        assert_eq!(signer.public_key().len(), 33);
+        // RFC 6979 signatures are deterministic.
+        assert_eq!(signer.sign("hello world"), signer.sign("hello world"));
+    }
+
+    #[cfg(feature = "p256")]
+    #[test]
+    fn test_ecdsa_p256_rejects_high_s() {
+        use crate::encoder::Base64;
+
+        let signer = ecdsa_p256_builder([3; 32]).build().unwrap();
+        let verifier = signer.verifier();
+        let signed = signer.sign("hello world");
+        let (value, signature) = signed.rsplit_once('.').unwrap();
+        let signature = Base64::decode::<typenum::U64>(signature.as_bytes()).unwrap();
+        let signature = p256::ecdsa::Signature::from_slice(&signature).unwrap();
+        // Signatures are made with a low S, so that there is only one for each value.
+        assert!(signature.normalize_s().is_none());
+
+        // (r, n - s) is also a valid ECDSA signature for the same value.
+        let (r, s) = signature.split_scalars();
+        let flipped = p256::ecdsa::Signature::from_scalars(r, -s).unwrap();
+        let mut tampered = String::from(value);
+        tampered.push('.');
+        Base64::encode_str(&flipped.to_bytes(), &mut tampered);
+        assert_ne!(tampered, signed);
+        assert!(signer.unsign(&tampered).err().unwrap().is_tampered());
+        assert!(verifier.unsign(&tampered).err().unwrap().is_tampered());
+        assert_eq!(verifier.unsign(&signed).unwrap(), "hello world");
+    }
+
+    #[cfg(feature = "ed25519")]
+    #[test]
+    #[should_panic(expected = "the separator must not be in the alphabet of the encoder")]
+    fn test_verifier_separator_in_encoder_alphabet() {
+        use crate::encoder::StandardBase64;
+
+        let signer = ed25519_builder([1; 32]).build().unwrap();
+        // '+' is not in the default url-safe alphabet, but it is in the standard one.
+        let _ = ed25519_verifier_builder(signer.public_key())
+            .with_separator(Separator::new('+').unwrap())
+            .with_encoder(StandardBase64)
+            .build();
+    }
+
+    #[cfg(feature = "p256")]
+    #[test]
+    fn test_ecdsa_p256_invalid_keys() {
+        // Zero is not a valid scalar.
+        assert_eq!(
+            ecdsa_p256_builder([0; 32]).build().err(),
+            Some(InvalidKey::PrivateKey(0))
+        );
+        assert_eq!(
+            ecdsa_p256_builder([0xff; 32]).build().err(),
+            Some(InvalidKey::PrivateKey(0))
+        );
+        assert_eq!(
+            ecdsa_p256_verifier_builder([4; 33]).build().err(),
+            Some(InvalidKey::PublicKey(0))
+        );
+    }
+}
@@ -1,55 +1,113 @@
-use std::ops::{Add, Div, Mul, Rem};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Rem};
 
 use hmac::digest::generic_array::typenum::*;
 
-use base64;
 pub use base64::DecodeError;
 
 use hmac::digest::generic_array::{ArrayLength, GenericArray};
 
-static BASE64_ALPHABET: &'static str =
-    "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789-_=";
+use crate::encoder::{Base64, Base64Alphabet, Base64Profile, DecodePadding};
 
-/// A trait that allows a type to be safely encoded as a url-safe
-/// base64 string.
-pub trait URLSafeBase64Encode: Sized {
-    #[cfg(test)]
-    fn base64_encode(self) -> String {
-        let mut target = String::new();
-        self.base64_encode_str(&mut target);
-        target
-    }
+/// Returns the configuration of the `base64` crate for a given profile.
+#[inline(always)]
+fn config<P: Base64Profile>() -> base64::Config {
+    let char_set = match P::ALPHABET {
+        Base64Alphabet::Standard => base64::CharacterSet::Standard,
+        Base64Alphabet::UrlSafe => base64::CharacterSet::UrlSafe,
+    };
+    base64::Config::new(char_set, P::PAD)
+}
 
-    fn base64_encode_str(self, target: &mut String);
+/// Returns the length of `input_len` bytes, once encoded as base64.
+#[inline(always)]
+pub(crate) fn encoded_len<P: Base64Profile>(input_len: usize) -> usize {
+    if P::PAD {
+        input_len.div_ceil(3) * 4
+    } else {
+        (input_len * 4).div_ceil(3)
+    }
 }
 
-/// Encodes a string as url safe base64.
+/// Encodes a string as base64.
 #[inline(always)]
 #[allow(dead_code)]
-pub(crate) fn encode<T>(input: &T) -> String
+pub(crate) fn encode<P, T>(input: &T) -> String
 where
+    P: Base64Profile,
     T: ?Sized + AsRef<[u8]>,
 {
-    base64::encode_config(input, base64::URL_SAFE_NO_PAD)
+    base64::encode_config(input, config::<P>())
 }
 
-/// Encodes a string as url safe base64.
+/// Encodes a string as base64.
 #[inline(always)]
-#[allow(dead_code)]
-pub(crate) fn encode_slice<T>(input: &T, target: &mut [u8]) -> usize
+pub(crate) fn encode_slice<P, T>(input: &T, target: &mut [u8]) -> usize
+where
+    P: Base64Profile,
+    T: ?Sized + AsRef<[u8]>,
+{
+    base64::encode_config_slice(input, config::<P>(), target)
+}
+
+/// Encodes a string as base64.
+#[inline(always)]
+pub(crate) fn encode_str<P, T>(input: &T, target: &mut String)
 where
+    P: Base64Profile,
     T: ?Sized + AsRef<[u8]>,
 {
-    base64::encode_config_slice(input, base64::URL_SAFE_NO_PAD, target)
+    base64::encode_config_buf(input, config::<P>(), target)
 }
 
-/// Encodes a string as url safe base64.
+/// Encodes a string as base64, appending it to a byte buffer.
 #[inline(always)]
-pub(crate) fn encode_str<T>(input: &T, target: &mut String)
+pub(crate) fn encode_vec<P, T>(input: &T, target: &mut Vec<u8>)
 where
+    P: Base64Profile,
     T: ?Sized + AsRef<[u8]>,
 {
-    base64::encode_config_buf(input, base64::URL_SAFE_NO_PAD, target)
+    let input = input.as_ref();
+    let start = target.len();
+    target.resize(start + encoded_len::<P>(input.len()), 0);
+    let length = encode_slice::<P, _>(input, &mut target[start..]);
+    target.truncate(start + length);
+}
+
+/// Encodes a string as base64, writing it to `target` without allocating.
+#[inline(always)]
+pub(crate) fn encode_fmt<P, W>(input: &[u8], target: &mut W) -> fmt::Result
+where
+    P: Base64Profile,
+    W: ?Sized + fmt::Write,
+{
+    let display = base64::display::Base64Display::with_config(input, config::<P>());
+    write!(target, "{}", display)
+}
+
+/// Checks the padding of `input` against the profile, and returns `input` without it.
+#[inline(always)]
+fn strip_padding<P: Base64Profile>(input: &[u8]) -> Result<&[u8], DecodeError> {
+    let unpadded_length = input
+        .iter()
+        .rposition(|&c| c != b'=')
+        .map_or(0, |index| index + 1);
+    let padding_length = input.len() - unpadded_length;
+
+    if padding_length == 0 {
+        if P::DECODE_PADDING == DecodePadding::Required && !input.len().is_multiple_of(4) {
+            return Err(DecodeError::InvalidLength);
+        }
+    } else if P::DECODE_PADDING == DecodePadding::Forbidden {
+        return Err(DecodeError::InvalidByte(unpadded_length, b'='));
+    } else if padding_length > 2 || !input.len().is_multiple_of(4) {
+        // Padding is only ever used to round the length up to a multiple of 4.
+        return Err(DecodeError::InvalidLength);
+    }
+
+    Ok(&input[..unpadded_length])
 }
 
 pub(crate) struct DecodeResult<N: ArrayLength<u8>> {
@@ -71,15 +129,16 @@ impl<N: ArrayLength<u8>> DecodeResult<N> {
     }
 }
 
-/// Decodes a base64 encoded string from `URLSafeBase64Encode` to a sized GenericArray.
+/// Decodes a base64 encoded string to a sized GenericArray.
 #[inline(always)]
-pub(crate) fn decode<N, T>(input: &T) -> Result<DecodeResult<N>, DecodeError>
+pub(crate) fn decode<P, N, T>(input: &T) -> Result<DecodeResult<N>, DecodeError>
 where
+    P: Base64Profile,
     N: ArrayLength<u8>,
     T: ?Sized + AsRef<[u8]>,
 {
     let mut array = GenericArray::default();
-    let input = input.as_ref();
+    let input = strip_padding::<P>(input.as_ref())?;
     let input_len = input.len();
     let output_len = array.len();
     let required_output_len = input_len / 4 * 3;
@@ -88,23 +147,30 @@ where
     if required_output_len > output_len {
         return Err(DecodeError::InvalidLength);
     }
-    let length = base64::decode_config_slice(input, base64::URL_SAFE_NO_PAD, &mut array)?;
+    let length = base64::decode_config_slice(input, config::<P>(), &mut array)?;
     Ok(DecodeResult { array, length })
 }
 
-/// Decodes a base64 encoded string from `URLSafeBase64Encode` to a sized GenericArray.
+/// Decodes a base64 encoded string to a `Vec`.
 #[inline(always)]
 #[allow(dead_code)]
-pub(crate) fn decode_str<T>(input: &T) -> Result<Vec<u8>, DecodeError>
+pub(crate) fn decode_str<P, T>(input: &T) -> Result<Vec<u8>, DecodeError>
 where
+    P: Base64Profile,
     T: ?Sized + AsRef<[u8]>,
 {
-    base64::decode_config(input, base64::URL_SAFE_NO_PAD)
+    base64::decode_config(strip_padding::<P>(input.as_ref())?, config::<P>())
 }
 
-/// Returns whether or not a given character is in the base64 alphabet.
-pub(crate) fn in_alphabet(c: char) -> bool {
-    BASE64_ALPHABET.contains(c)
+/// Returns whether or not a given character can appear in a value encoded using a profile.
+pub(crate) fn in_alphabet<P: Base64Profile>(c: char) -> bool {
+    match c {
+        'a'..='z' | 'A'..='Z' | '0'..='9' => true,
+        '+' | '/' => P::ALPHABET == Base64Alphabet::Standard,
+        '-' | '_' => P::ALPHABET == Base64Alphabet::UrlSafe,
+        '=' => P::PAD || P::DECODE_PADDING != DecodePadding::Forbidden,
+        _ => false,
+    }
 }
 
 /// A trait that is implemented by `Base64SizedEncoder` that provides facilities
@@ -114,7 +180,9 @@ pub trait Base64Sized {
     type InputSize: ArrayLength<u8>;
     type OutputSize: ArrayLength<u8>;
 
+    #[allow(dead_code)]
     fn encode(input: GenericArray<u8, Self::InputSize>) -> GenericArray<u8, Self::OutputSize>;
+    #[allow(dead_code)]
     fn output_size() -> usize;
 }
 
@@ -173,7 +241,7 @@ where
 
     fn encode(input: GenericArray<u8, Self::InputSize>) -> GenericArray<u8, Self::OutputSize> {
         let mut output = GenericArray::default();
-        let size = encode_slice(input.as_slice(), output.as_mut_slice());
+        let size = encode_slice::<Base64, _>(input.as_slice(), output.as_mut_slice());
         debug_assert_eq!(size, Self::OutputSize::to_usize());
         output
     }
@@ -192,7 +260,7 @@ mod tests {
         fn gen_string<N: ArrayLength<u8>>() -> GenericArray<u8, N> {
             let mut output: GenericArray<_, _> = Default::default();
             for c in output.as_mut_slice() {
-                *c = 'a' as u8;
+                *c = b'a';
             }
             output
         }
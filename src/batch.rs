@@ -0,0 +1,26 @@
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Applies `f` to each of `values`, returning the results in the same order.
+///
+/// If the `rayon` feature is enabled, the values are processed on rayon's global
+/// thread pool.
+pub(crate) fn map_in_order<I, R, F>(values: I, f: F) -> Vec<R>
+where
+    I: IntoIterator,
+    I::Item: Send,
+    R: Send,
+    F: Fn(I::Item) -> R + Sync + Send,
+{
+    #[cfg(feature = "rayon")]
+    {
+        let values: Vec<_> = values.into_iter().collect();
+        values.into_par_iter().map(f).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        values.into_iter().map(f).collect()
+    }
+}
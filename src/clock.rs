@@ -0,0 +1,129 @@
+//! Clocks, which timed signers take the current time from.
+//!
+//! Every time-dependent operation of a [`TimestampSigner`] or [`TimestampVerifier`], such as
+//! [`TimestampSigner::sign`] and [`UnsignedValue::value_if_not_expired`], reads the current
+//! time from its [`Clock`]. This is the [`SystemClock`] by default, and can be replaced using
+//! [`SignerBuilder::with_clock`], or the `with_clock` method of a timed signer, e.g. to test
+//! expiry or to simulate clock skew.
+//!
+//! Requires the `std` feature. Without it, there is no current time, so timestamps are always
+//! supplied by the caller, e.g. using [`TimestampSigner::sign_with_timestamp`].
+//!
+//! # Example
+//! ```rust
+//! use std::time::Duration;
+//! use itsdangerous::clock::ManualClock;
+//! use itsdangerous::time::UNIX_EPOCH;
+//! use itsdangerous::{default_builder, IntoTimestampSigner, TimestampSigner};
+//!
+//! let clock = ManualClock::new(UNIX_EPOCH + Duration::from_secs(1560181622));
+//! let signer = default_builder("secret key")
+//!     .with_clock(clock.clone())
+//!     .build()
+//!     .into_timestamp_signer();
+//! let signed = signer.sign("hello world!");
+//!
+//! clock.advance(Duration::from_secs(61));
+//! let unsigned = signer.unsign(&signed).unwrap();
+//! assert!(unsigned.value_if_not_expired(Duration::from_secs(60)).is_err());
+//! ```
+//!
+//! [`TimestampSigner`]: crate::TimestampSigner
+//! [`TimestampVerifier`]: crate::TimestampVerifier
+//! [`TimestampSigner::sign`]: crate::TimestampSigner::sign
+//! [`TimestampSigner::sign_with_timestamp`]: crate::TimestampSigner::sign_with_timestamp
+//! [`UnsignedValue::value_if_not_expired`]: crate::UnsignedValue::value_if_not_expired
+//! [`SignerBuilder::with_clock`]: crate::SignerBuilder::with_clock
+
+use std::sync::{Arc, Mutex};
+
+use crate::time::{Duration, SystemTime};
+
+/// A source of the current time.
+///
+/// Clocks are shared by signers, which can be used from several threads, so they must be
+/// `Send` and `Sync`.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// A clock which returns the current system time, as provided by [`SystemTime::now`]. This is
+/// the default.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SystemClock;
+
+/// A clock which always returns the same time.
+#[derive(Debug, Copy, Clone)]
+pub struct FixedClock(pub SystemTime);
+
+/// A clock which returns a time that is set, or advanced, manually. Clones of a manual clock
+/// share the same time, so a clone can be given to a signer while the original is used to
+/// control it.
+#[derive(Debug, Clone)]
+pub struct ManualClock(Arc<Mutex<SystemTime>>);
+
+/// Returns the clock that is used when none is given.
+pub(crate) fn default_clock() -> Box<dyn Clock> {
+    Box::new(SystemClock)
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+impl ManualClock {
+    /// Constructs a new manual clock, which returns `now` until it is changed.
+    pub fn new(now: SystemTime) -> Self {
+        ManualClock(Arc::new(Mutex::new(now)))
+    }
+
+    /// Sets the time that the clock returns.
+    pub fn set(&self, now: SystemTime) {
+        *self.0.lock().unwrap() = now;
+    }
+
+    /// Moves the time that the clock returns forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_manual_clock() {
+        let clock = ManualClock::new(UNIX_EPOCH);
+        let clone = clock.clone();
+        assert_eq!(clone.now(), UNIX_EPOCH);
+
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(clone.now(), UNIX_EPOCH + Duration::from_secs(5));
+
+        clone.set(UNIX_EPOCH + Duration::from_secs(60));
+        assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_fixed_clock() {
+        let fixed = FixedClock(UNIX_EPOCH);
+        assert_eq!(fixed.now(), UNIX_EPOCH);
+    }
+}
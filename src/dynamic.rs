@@ -0,0 +1,503 @@
+//! Object-safe versions of the signer and serializer traits.
+//!
+//! [`Signer`], [`TimestampSigner`] and [`Serializer`] have generic methods, which means that
+//! they cannot be used as trait objects. The traits in this module mirror their methods using
+//! concrete types instead, and are implemented for every type that implements the original
+//! trait. This allows a signer to be stored as, for example, an `Arc<dyn DynSigner + Send + Sync>`
+//! without having to name its (rather long) concrete type.
+//!
+//! # Remarks
+//! Because the traits in this module share method names with the traits they mirror, having
+//! both in scope makes method calls on concrete signers ambiguous. They are therefore not
+//! re-exported from the crate root, and are best imported only where trait objects are used.
+//!
+//! # Example
+//! ```rust
+//! use std::sync::Arc;
+//! use itsdangerous::default_builder;
+//! use itsdangerous::dynamic::DynSigner;
+//!
+//! struct AppState {
+//!     signer: Arc<dyn DynSigner + Send + Sync>,
+//! }
+//!
+//! let state = AppState {
+//!     signer: Arc::new(default_builder("secret key").build()),
+//! };
+//!
+//! let signed = state.signer.sign("hello world!");
+//! assert_eq!(state.signer.unsign(&signed).unwrap(), "hello world!");
+//! ```
+//!
+//! [`Signer`]: crate::Signer
+//! [`TimestampSigner`]: crate::TimestampSigner
+//! [`Serializer`]: crate::Serializer
+
+use crate::time::SystemTime;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use crate::clock::Clock;
+#[cfg(feature = "std")]
+use crate::ExpiryPolicy;
+use crate::{
+    BadSignature, BadTimedSignature, BufferTooSmall, ExpiringValue, Separator, Signer,
+    TimestampSigner, UnsignedValue,
+};
+
+#[cfg(feature = "serializer")]
+use crate::Serializer;
+
+/// An object-safe version of [`Signer`].
+pub trait DynSigner {
+    /// See [`Signer::sign`].
+    fn sign(&self, value: &str) -> String;
+
+    /// See [`Signer::sign_into`].
+    fn sign_into(&self, value: &str, target: &mut String);
+
+    /// See [`Signer::sign_to_fmt`].
+    fn sign_to_fmt(&self, value: &str, target: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// See [`Signer::sign_to_slice`].
+    fn sign_to_slice(&self, value: &[u8], target: &mut [u8]) -> Result<usize, BufferTooSmall>;
+
+    /// See [`Signer::unsign`].
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<&'a str, BadSignature<'a>>;
+
+    /// See [`Signer::sign_bytes`].
+    fn sign_bytes(&self, value: &[u8]) -> Vec<u8>;
+
+    /// See [`Signer::unsign_bytes`].
+    fn unsign_bytes<'a>(&'a self, value: &'a [u8]) -> Result<&'a [u8], BadSignature<'a, [u8]>>;
+
+    /// See [`Signer::sign_detached`].
+    fn sign_detached(&self, value: &[u8]) -> String;
+
+    /// See [`Signer::verify_detached`].
+    fn verify_detached<'a>(
+        &'a self,
+        value: &'a str,
+        signature: &'a str,
+    ) -> Result<(), BadSignature<'a>>;
+
+    /// See [`Signer::separator`].
+    fn separator(&self) -> &Separator;
+
+    /// See [`Signer::verify_encoded_signature`].
+    fn verify_encoded_signature(&self, value: &[u8], encoded_signature: &[u8]) -> bool;
+
+    /// See [`Signer::signature_output_size`].
+    fn signature_output_size(&self) -> usize;
+}
+
+impl<T> DynSigner for T
+where
+    T: Signer,
+{
+    fn sign(&self, value: &str) -> String {
+        Signer::sign(self, value)
+    }
+
+    fn sign_into(&self, value: &str, target: &mut String) {
+        Signer::sign_into(self, value, target)
+    }
+
+    fn sign_to_fmt(&self, value: &str, target: &mut dyn fmt::Write) -> fmt::Result {
+        Signer::sign_to_fmt(self, value, target)
+    }
+
+    fn sign_to_slice(&self, value: &[u8], target: &mut [u8]) -> Result<usize, BufferTooSmall> {
+        Signer::sign_to_slice(self, value, target)
+    }
+
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<&'a str, BadSignature<'a>> {
+        Signer::unsign(self, value)
+    }
+
+    fn sign_bytes(&self, value: &[u8]) -> Vec<u8> {
+        Signer::sign_bytes(self, value)
+    }
+
+    fn unsign_bytes<'a>(&'a self, value: &'a [u8]) -> Result<&'a [u8], BadSignature<'a, [u8]>> {
+        Signer::unsign_bytes(self, value)
+    }
+
+    fn sign_detached(&self, value: &[u8]) -> String {
+        Signer::sign_detached(self, value)
+    }
+
+    fn verify_detached<'a>(
+        &'a self,
+        value: &'a str,
+        signature: &'a str,
+    ) -> Result<(), BadSignature<'a>> {
+        Signer::verify_detached(self, value, signature)
+    }
+
+    fn separator(&self) -> &Separator {
+        Signer::separator(self)
+    }
+
+    fn verify_encoded_signature(&self, value: &[u8], encoded_signature: &[u8]) -> bool {
+        Signer::verify_encoded_signature(self, value, encoded_signature)
+    }
+
+    fn signature_output_size(&self) -> usize {
+        Signer::signature_output_size(self)
+    }
+}
+
+/// An object-safe version of [`TimestampSigner`].
+pub trait DynTimestampSigner {
+    /// See [`TimestampSigner::separator`].
+    fn separator(&self) -> &Separator;
+
+    /// See [`TimestampSigner::clock`].
+    #[cfg(feature = "std")]
+    fn clock(&self) -> &dyn Clock;
+
+    /// See [`TimestampSigner::expiry_policy`].
+    #[cfg(feature = "std")]
+    fn expiry_policy(&self) -> Option<&ExpiryPolicy>;
+
+    /// See [`TimestampSigner::sign_with_timestamp`].
+    fn sign_with_timestamp(&self, value: &str, timestamp: SystemTime) -> String;
+
+    /// See [`TimestampSigner::sign_with_timestamp_into`].
+    fn sign_with_timestamp_into(&self, value: &str, timestamp: SystemTime, target: &mut String);
+
+    /// See [`TimestampSigner::sign_with_timestamp_to_fmt`].
+    fn sign_with_timestamp_to_fmt(
+        &self,
+        value: &str,
+        timestamp: SystemTime,
+        target: &mut dyn fmt::Write,
+    ) -> fmt::Result;
+
+    /// See [`TimestampSigner::sign_with_timestamp_to_slice`].
+    fn sign_with_timestamp_to_slice(
+        &self,
+        value: &[u8],
+        timestamp: SystemTime,
+        target: &mut [u8],
+    ) -> Result<usize, BufferTooSmall>;
+
+    /// See [`TimestampSigner::sign`].
+    #[cfg(feature = "std")]
+    fn sign(&self, value: &str) -> String;
+
+    /// See [`TimestampSigner::sign_into`].
+    #[cfg(feature = "std")]
+    fn sign_into(&self, value: &str, target: &mut String);
+
+    /// See [`TimestampSigner::sign_to_fmt`].
+    #[cfg(feature = "std")]
+    fn sign_to_fmt(&self, value: &str, target: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// See [`TimestampSigner::sign_to_slice`].
+    #[cfg(feature = "std")]
+    fn sign_to_slice(&self, value: &[u8], target: &mut [u8]) -> Result<usize, BufferTooSmall>;
+
+    /// See [`TimestampSigner::unsign`].
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<UnsignedValue<'a>, BadTimedSignature<'a>>;
+
+    /// See [`TimestampSigner::sign_bytes_with_timestamp`].
+    fn sign_bytes_with_timestamp(&self, value: &[u8], timestamp: SystemTime) -> Vec<u8>;
+
+    /// See [`TimestampSigner::sign_bytes`].
+    #[cfg(feature = "std")]
+    fn sign_bytes(&self, value: &[u8]) -> Vec<u8>;
+
+    /// See [`TimestampSigner::unsign_bytes`].
+    fn unsign_bytes<'a>(
+        &'a self,
+        value: &'a [u8],
+    ) -> Result<UnsignedValue<'a, [u8]>, BadTimedSignature<'a, [u8]>>;
+
+    /// See [`TimestampSigner::sign_detached_with_timestamp`].
+    fn sign_detached_with_timestamp(&self, value: &[u8], timestamp: SystemTime)
+        -> (String, String);
+
+    /// See [`TimestampSigner::sign_detached`].
+    #[cfg(feature = "std")]
+    fn sign_detached(&self, value: &[u8]) -> (String, String);
+
+    /// See [`TimestampSigner::verify_detached`].
+    fn verify_detached<'a>(
+        &'a self,
+        value: &'a str,
+        timestamp: &'a str,
+        signature: &'a str,
+    ) -> Result<UnsignedValue<'a>, BadTimedSignature<'a>>;
+
+    /// See [`TimestampSigner::sign_with_expiry`].
+    fn sign_with_expiry(&self, value: &str, expires_at: SystemTime) -> String;
+
+    /// See [`TimestampSigner::sign_with_validity`].
+    fn sign_with_validity(
+        &self,
+        value: &str,
+        not_before: SystemTime,
+        expires_at: SystemTime,
+    ) -> String;
+
+    /// See [`TimestampSigner::unsign_with_expiry`].
+    #[cfg(feature = "std")]
+    fn unsign_with_expiry<'a>(
+        &'a self,
+        value: &'a str,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>>;
+
+    /// See [`TimestampSigner::unsign_with_expiry_at`].
+    fn unsign_with_expiry_at<'a>(
+        &'a self,
+        value: &'a str,
+        now: SystemTime,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>>;
+}
+
+impl<T> DynTimestampSigner for T
+where
+    T: TimestampSigner,
+{
+    fn separator(&self) -> &Separator {
+        TimestampSigner::separator(self)
+    }
+
+    #[cfg(feature = "std")]
+    fn clock(&self) -> &dyn Clock {
+        TimestampSigner::clock(self)
+    }
+
+    #[cfg(feature = "std")]
+    fn expiry_policy(&self) -> Option<&ExpiryPolicy> {
+        TimestampSigner::expiry_policy(self)
+    }
+
+    fn sign_with_timestamp(&self, value: &str, timestamp: SystemTime) -> String {
+        TimestampSigner::sign_with_timestamp(self, value, timestamp)
+    }
+
+    fn sign_with_timestamp_into(&self, value: &str, timestamp: SystemTime, target: &mut String) {
+        TimestampSigner::sign_with_timestamp_into(self, value, timestamp, target)
+    }
+
+    fn sign_with_timestamp_to_fmt(
+        &self,
+        value: &str,
+        timestamp: SystemTime,
+        target: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        TimestampSigner::sign_with_timestamp_to_fmt(self, value, timestamp, target)
+    }
+
+    fn sign_with_timestamp_to_slice(
+        &self,
+        value: &[u8],
+        timestamp: SystemTime,
+        target: &mut [u8],
+    ) -> Result<usize, BufferTooSmall> {
+        TimestampSigner::sign_with_timestamp_to_slice(self, value, timestamp, target)
+    }
+
+    #[cfg(feature = "std")]
+    fn sign(&self, value: &str) -> String {
+        TimestampSigner::sign(self, value)
+    }
+
+    #[cfg(feature = "std")]
+    fn sign_into(&self, value: &str, target: &mut String) {
+        TimestampSigner::sign_into(self, value, target)
+    }
+
+    #[cfg(feature = "std")]
+    fn sign_to_fmt(&self, value: &str, target: &mut dyn fmt::Write) -> fmt::Result {
+        TimestampSigner::sign_to_fmt(self, value, target)
+    }
+
+    #[cfg(feature = "std")]
+    fn sign_to_slice(&self, value: &[u8], target: &mut [u8]) -> Result<usize, BufferTooSmall> {
+        TimestampSigner::sign_to_slice(self, value, target)
+    }
+
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<UnsignedValue<'a>, BadTimedSignature<'a>> {
+        TimestampSigner::unsign(self, value)
+    }
+
+    fn sign_bytes_with_timestamp(&self, value: &[u8], timestamp: SystemTime) -> Vec<u8> {
+        TimestampSigner::sign_bytes_with_timestamp(self, value, timestamp)
+    }
+
+    #[cfg(feature = "std")]
+    fn sign_bytes(&self, value: &[u8]) -> Vec<u8> {
+        TimestampSigner::sign_bytes(self, value)
+    }
+
+    fn unsign_bytes<'a>(
+        &'a self,
+        value: &'a [u8],
+    ) -> Result<UnsignedValue<'a, [u8]>, BadTimedSignature<'a, [u8]>> {
+        TimestampSigner::unsign_bytes(self, value)
+    }
+
+    fn sign_detached_with_timestamp(
+        &self,
+        value: &[u8],
+        timestamp: SystemTime,
+    ) -> (String, String) {
+        TimestampSigner::sign_detached_with_timestamp(self, value, timestamp)
+    }
+
+    #[cfg(feature = "std")]
+    fn sign_detached(&self, value: &[u8]) -> (String, String) {
+        TimestampSigner::sign_detached(self, value)
+    }
+
+    fn verify_detached<'a>(
+        &'a self,
+        value: &'a str,
+        timestamp: &'a str,
+        signature: &'a str,
+    ) -> Result<UnsignedValue<'a>, BadTimedSignature<'a>> {
+        TimestampSigner::verify_detached(self, value, timestamp, signature)
+    }
+
+    fn sign_with_expiry(&self, value: &str, expires_at: SystemTime) -> String {
+        TimestampSigner::sign_with_expiry(self, value, expires_at)
+    }
+
+    fn sign_with_validity(
+        &self,
+        value: &str,
+        not_before: SystemTime,
+        expires_at: SystemTime,
+    ) -> String {
+        TimestampSigner::sign_with_validity(self, value, not_before, expires_at)
+    }
+
+    #[cfg(feature = "std")]
+    fn unsign_with_expiry<'a>(
+        &'a self,
+        value: &'a str,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>> {
+        TimestampSigner::unsign_with_expiry(self, value)
+    }
+
+    fn unsign_with_expiry_at<'a>(
+        &'a self,
+        value: &'a str,
+        now: SystemTime,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>> {
+        TimestampSigner::unsign_with_expiry_at(self, value, now)
+    }
+}
+
+/// An object-safe version of [`Serializer`].
+///
+/// Values are passed as a [`serde_json::Value`], which can be converted from and into any
+/// serializable type using [`serde_json::to_value`] and [`serde_json::from_value`].
+///
+/// # Remarks
+/// Objects in a [`serde_json::Value`] have their keys sorted, so the payload that is signed
+/// may differ from the one [`Serializer::sign`] would have produced for the same struct. Both
+/// can be unsigned by either trait.
+#[cfg(feature = "serializer")]
+pub trait DynSerializer {
+    /// See [`Serializer::sign`].
+    fn sign(&self, value: &serde_json::Value) -> serde_json::Result<String>;
+
+    /// See [`Serializer::unsign`].
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<serde_json::Value, BadSignature<'a>>;
+}
+
+#[cfg(feature = "serializer")]
+impl<T> DynSerializer for T
+where
+    T: Serializer,
+{
+    fn sign(&self, value: &serde_json::Value) -> serde_json::Result<String> {
+        Serializer::sign(self, value)
+    }
+
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<serde_json::Value, BadSignature<'a>> {
+        Serializer::unsign(self, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::string::String;
+    use alloc::sync::Arc;
+
+    use crate::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+    use crate::{default_builder, IntoTimestampSigner};
+
+    #[test]
+    fn test_dyn_signer() {
+        let signer: Arc<dyn DynSigner + Send + Sync> = Arc::new(default_builder("hello").build());
+        let signed = signer.sign("this is a test");
+        assert_eq!(signed, "this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0");
+        assert_eq!(signer.unsign(&signed).unwrap(), "this is a test");
+        assert!(signer.unsign("this is a test.nope").is_err());
+
+        let mut target = String::new();
+        signer.sign_to_fmt("this is a test", &mut target).unwrap();
+        assert_eq!(target, signed);
+    }
+
+    #[test]
+    fn test_dyn_timestamp_signer() {
+        let signer: Box<dyn DynTimestampSigner> =
+            Box::new(default_builder("hello").build().into_timestamp_signer());
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+        assert_eq!(signed, "hello world.XP57dg.uBK_KvrfABr48ZHk6IrBINjpqp8");
+
+        let mut buffer = [0; 64];
+        let length = signer
+            .sign_with_timestamp_to_slice(b"hello world", timestamp, &mut buffer)
+            .unwrap();
+        assert_eq!(&buffer[..length], signed.as_bytes());
+
+        let unsigned = signer.unsign(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.timestamp(), timestamp);
+
+        let expires_at = timestamp + Duration::from_secs(60);
+        let token = signer.sign_with_expiry("hello world", expires_at);
+        let unsigned = signer.unsign_with_expiry_at(&token, timestamp).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.expires_at(), expires_at);
+        assert!(signer
+            .unsign_with_expiry_at(&token, expires_at)
+            .unwrap_err()
+            .is_expired());
+
+        let token = signer.sign_with_validity("hello world", expires_at, expires_at);
+        assert!(signer.unsign_with_expiry_at(&token, timestamp).is_err());
+    }
+
+    #[cfg(feature = "serializer")]
+    #[test]
+    fn test_dyn_serializer() {
+        use crate::{serializer_with_signer, NullEncoding};
+
+        let serializer: Box<dyn DynSerializer> = Box::new(serializer_with_signer(
+            default_builder("hello world").build(),
+            NullEncoding,
+        ));
+        let value = serde_json::to_value(vec![1, 2, 3]).unwrap();
+        let signed = serializer.sign(&value).unwrap();
+        assert_eq!(signed, "[1,2,3].bq_ST5hV4J35lKdovyr_ng-ZIxU");
+
+        let unsigned = serializer.unsign(&signed).unwrap();
+        let unsigned: Vec<u8> = serde_json::from_value(unsigned).unwrap();
+        assert_eq!(unsigned, vec![1, 2, 3]);
+    }
+}
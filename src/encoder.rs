@@ -0,0 +1,644 @@
+//! Encoders for the signature part of a signed value.
+//!
+//! By default, signatures are encoded using url-safe base64 without padding, which is
+//! what the python library does. A different encoder can be chosen using
+//! [`SignerBuilder::with_encoder`], in which case a [`Separator`] that is valid for that
+//! encoder can be created using [`Separator::for_encoder`].
+//!
+//! # Example
+//! ```rust
+//! use itsdangerous::encoder::Hex;
+//! use itsdangerous::{default_builder, Separator, Signer};
+//!
+//! let signer = default_builder("secret key")
+//!     .with_encoder(Hex)
+//!     .with_separator(Separator::for_encoder::<Hex>(':').unwrap())
+//!     .build();
+//! let signed = signer.sign("hello world!");
+//! assert_eq!(signer.unsign(&signed).unwrap(), "hello world!");
+//! ```
+//!
+//! Every [`Base64Profile`] is also an encoder, which changes the alphabet and padding used for
+//! both signatures and timestamps. For example, [`StandardBase64`] uses the standard base64
+//! alphabet with padding:
+//! ```rust
+//! use itsdangerous::encoder::StandardBase64;
+//! use itsdangerous::{default_builder, Separator, Signer};
+//!
+//! let signer = default_builder("secret key")
+//!     .with_encoder(StandardBase64)
+//!     .with_separator(Separator::for_encoder::<StandardBase64>('.').unwrap())
+//!     .build();
+//! let signed = signer.sign("hello world!");
+//! assert!(signed.ends_with('='));
+//! assert_eq!(signer.unsign(&signed).unwrap(), "hello world!");
+//! ```
+//!
+//! [`SignerBuilder::with_encoder`]: crate::SignerBuilder::with_encoder
+//! [`Separator`]: crate::Separator
+//! [`Separator::for_encoder`]: crate::Separator::for_encoder
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::{fmt, str};
+use generic_array::{ArrayLength, GenericArray};
+
+use crate::base64;
+
+/// A trait which implements the encoding of signatures to text, and decoding them back.
+pub trait SignatureEncoder {
+    /// The base64 profile that a [`TimestampSigner`] using this encoder encodes timestamps
+    /// with. This is [`Base64`] for encoders that are not themselves a [`Base64Profile`].
+    ///
+    /// [`TimestampSigner`]: crate::TimestampSigner
+    type TimestampProfile: Base64Profile;
+
+    /// Returns the length of the encoded form of `input_len` bytes.
+    fn encoded_len(input_len: usize) -> usize;
+
+    /// Encodes `input`, appending it to `target`.
+    fn encode_str(input: &[u8], target: &mut String);
+
+    /// Encodes `input`, appending it to `target`.
+    fn encode_vec(input: &[u8], target: &mut Vec<u8>);
+
+    /// Encodes `input` into the start of `target`, returning the number of bytes written.
+    ///
+    /// # Panics
+    /// Panics if `target` is shorter than [`encoded_len`] bytes.
+    ///
+    /// [`encoded_len`]: SignatureEncoder::encoded_len
+    fn encode_slice(input: &[u8], target: &mut [u8]) -> usize {
+        let mut output = Vec::with_capacity(Self::encoded_len(input.len()));
+        Self::encode_vec(input, &mut output);
+        target[..output.len()].copy_from_slice(&output);
+        output.len()
+    }
+
+    /// Encodes `input`, writing it to `target`.
+    ///
+    /// The built-in encoders override this and [`encode_slice`] so that neither allocates,
+    /// while the default implementations encode into a temporary buffer.
+    ///
+    /// [`encode_slice`]: SignatureEncoder::encode_slice
+    fn encode_fmt<W: ?Sized + fmt::Write>(input: &[u8], target: &mut W) -> fmt::Result {
+        let mut output = String::with_capacity(Self::encoded_len(input.len()));
+        Self::encode_str(input, &mut output);
+        target.write_str(&output)
+    }
+
+    /// Decodes `input`, returning `None` if it is not the encoded form of exactly `N` bytes.
+    fn decode<N: ArrayLength<u8>>(input: &[u8]) -> Option<GenericArray<u8, N>>;
+
+    /// Returns whether or not a given character can appear in an encoded value. A
+    /// separator that is used with this encoder must not be in its alphabet.
+    fn in_alphabet(c: char) -> bool;
+}
+
+/// The alphabet of a [`Base64Profile`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Base64Alphabet {
+    /// The standard alphabet, which uses `+` and `/`.
+    Standard,
+    /// The url-safe alphabet, which uses `-` and `_`.
+    UrlSafe,
+}
+
+/// Whether a [`Base64Profile`] accepts padding when decoding.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DecodePadding {
+    /// Accepts values both with and without padding. Padding must be canonical if present.
+    Optional,
+    /// Only accepts values with canonical padding.
+    Required,
+    /// Only accepts values without padding.
+    Forbidden,
+}
+
+/// A base64 profile, which configures the alphabet and the padding of base64 encoded values.
+///
+/// Every profile can be used as a [`SignatureEncoder`], in which case timestamps are encoded
+/// with it as well, and as the payload encoding of a serializer using [`Base64Encoding`].
+///
+/// # Example
+/// ```rust
+/// use itsdangerous::encoder::{Base64Alphabet, Base64Profile, DecodePadding};
+/// use itsdangerous::{default_builder, Signer};
+///
+/// /// Url-safe base64, emitted without padding, but accepted with it.
+/// #[derive(Debug, Default)]
+/// struct Lenient;
+///
+/// impl Base64Profile for Lenient {
+///     const ALPHABET: Base64Alphabet = Base64Alphabet::UrlSafe;
+///     const PAD: bool = false;
+///     const DECODE_PADDING: DecodePadding = DecodePadding::Optional;
+/// }
+///
+/// let signer = default_builder("secret key").with_encoder(Lenient).build();
+/// assert_eq!(signer.unsign("hello.Cihu8w4HvBqXWE64zLZ9utErhLA=").unwrap(), "hello");
+/// assert_eq!(signer.unsign("hello.Cihu8w4HvBqXWE64zLZ9utErhLA").unwrap(), "hello");
+/// ```
+///
+/// [`Base64Encoding`]: crate::Base64Encoding
+pub trait Base64Profile {
+    /// The alphabet that values are encoded with.
+    const ALPHABET: Base64Alphabet;
+    /// Whether or not padding is emitted when encoding.
+    const PAD: bool;
+    /// Whether or not padding is accepted when decoding.
+    const DECODE_PADDING: DecodePadding;
+}
+
+/// Encodes signatures using url-safe base64 without padding. This is the default, and is
+/// compatible with the python library, which also accepts padding when decoding.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Base64;
+
+/// Encodes signatures using the standard base64 alphabet, with padding, which is required
+/// when decoding.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct StandardBase64;
+
+/// Encodes signatures using url-safe base64 with padding, which is required when decoding.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct UrlSafePaddedBase64;
+
+/// Encodes signatures using lowercase hexadecimal, for systems that treat tokens
+/// case-insensitively. Uppercase signatures are also accepted when decoding.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Hex;
+
+/// Encodes signatures using [Crockford's base32], without padding, which is suited to codes
+/// that are typed by hand.
+///
+/// Signatures are encoded in uppercase. Decoding is case-insensitive, and accepts `O` in place
+/// of `0` and `I` or `L` in place of `1`.
+///
+/// [Crockford's base32]: https://www.crockford.com/base32.html
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CrockfordBase32;
+
+/// Encodes signatures using base62 (`0-9`, `A-Z` then `a-z`), which contains no punctuation
+/// at all.
+///
+/// The signature is treated as a big-endian number, which is encoded using as many digits
+/// as the largest signature of that size requires, padded with leading `0`s.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Base62;
+
+static HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+static CROCKFORD_BASE32_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+static BASE62_ALPHABET: &[u8; 62] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+/// `log2(62)` as a 32.32 fixed point number, rounded down, as `core` has no logarithms.
+const LOG2_62: u64 = 25_573_078_427;
+
+impl Base64Profile for Base64 {
+    const ALPHABET: Base64Alphabet = Base64Alphabet::UrlSafe;
+    const PAD: bool = false;
+    const DECODE_PADDING: DecodePadding = DecodePadding::Optional;
+}
+
+impl Base64Profile for StandardBase64 {
+    const ALPHABET: Base64Alphabet = Base64Alphabet::Standard;
+    const PAD: bool = true;
+    const DECODE_PADDING: DecodePadding = DecodePadding::Required;
+}
+
+impl Base64Profile for UrlSafePaddedBase64 {
+    const ALPHABET: Base64Alphabet = Base64Alphabet::UrlSafe;
+    const PAD: bool = true;
+    const DECODE_PADDING: DecodePadding = DecodePadding::Required;
+}
+
+impl<Profile: Base64Profile> SignatureEncoder for Profile {
+    type TimestampProfile = Self;
+
+    fn encoded_len(input_len: usize) -> usize {
+        base64::encoded_len::<Self>(input_len)
+    }
+
+    #[inline(always)]
+    fn encode_str(input: &[u8], target: &mut String) {
+        base64::encode_str::<Self, _>(input, target)
+    }
+
+    #[inline(always)]
+    fn encode_vec(input: &[u8], target: &mut Vec<u8>) {
+        base64::encode_vec::<Self, _>(input, target)
+    }
+
+    #[inline(always)]
+    fn encode_slice(input: &[u8], target: &mut [u8]) -> usize {
+        base64::encode_slice::<Self, _>(input, target)
+    }
+
+    #[inline(always)]
+    fn encode_fmt<W: ?Sized + fmt::Write>(input: &[u8], target: &mut W) -> fmt::Result {
+        base64::encode_fmt::<Self, _>(input, target)
+    }
+
+    #[inline(always)]
+    fn decode<N: ArrayLength<u8>>(input: &[u8]) -> Option<GenericArray<u8, N>> {
+        base64::decode::<Self, N, _>(input)
+            .and_then(|result| result.into_exact_inner())
+            .ok()
+    }
+
+    fn in_alphabet(c: char) -> bool {
+        base64::in_alphabet::<Self>(c)
+    }
+}
+
+impl SignatureEncoder for Hex {
+    type TimestampProfile = Base64;
+
+    fn encoded_len(input_len: usize) -> usize {
+        input_len * 2
+    }
+
+    fn encode_str(input: &[u8], target: &mut String) {
+        encode_bits(input, 4, HEX_ALPHABET, |c| target.push(c as char))
+    }
+
+    fn encode_vec(input: &[u8], target: &mut Vec<u8>) {
+        encode_bits(input, 4, HEX_ALPHABET, |c| target.push(c))
+    }
+
+    fn encode_slice(input: &[u8], target: &mut [u8]) -> usize {
+        encode_bits_slice(input, 4, HEX_ALPHABET, target)
+    }
+
+    fn encode_fmt<W: ?Sized + fmt::Write>(input: &[u8], target: &mut W) -> fmt::Result {
+        encode_bits_fmt(input, 4, HEX_ALPHABET, target)
+    }
+
+    fn decode<N: ArrayLength<u8>>(input: &[u8]) -> Option<GenericArray<u8, N>> {
+        decode_bits::<Self, N, _>(input, 4, |c| (c as char).to_digit(16).map(|d| d as u8))
+    }
+
+    fn in_alphabet(c: char) -> bool {
+        c.is_ascii_hexdigit()
+    }
+}
+
+impl SignatureEncoder for CrockfordBase32 {
+    type TimestampProfile = Base64;
+
+    fn encoded_len(input_len: usize) -> usize {
+        (input_len * 8).div_ceil(5)
+    }
+
+    fn encode_str(input: &[u8], target: &mut String) {
+        encode_bits(input, 5, CROCKFORD_BASE32_ALPHABET, |c| {
+            target.push(c as char)
+        })
+    }
+
+    fn encode_vec(input: &[u8], target: &mut Vec<u8>) {
+        encode_bits(input, 5, CROCKFORD_BASE32_ALPHABET, |c| target.push(c))
+    }
+
+    fn encode_slice(input: &[u8], target: &mut [u8]) -> usize {
+        encode_bits_slice(input, 5, CROCKFORD_BASE32_ALPHABET, target)
+    }
+
+    fn encode_fmt<W: ?Sized + fmt::Write>(input: &[u8], target: &mut W) -> fmt::Result {
+        encode_bits_fmt(input, 5, CROCKFORD_BASE32_ALPHABET, target)
+    }
+
+    fn decode<N: ArrayLength<u8>>(input: &[u8]) -> Option<GenericArray<u8, N>> {
+        decode_bits::<Self, N, _>(input, 5, |c| match c.to_ascii_uppercase() {
+            b'O' => Some(0),
+            b'I' | b'L' => Some(1),
+            c => CROCKFORD_BASE32_ALPHABET
+                .iter()
+                .position(|&d| d == c)
+                .map(|d| d as u8),
+        })
+    }
+
+    fn in_alphabet(c: char) -> bool {
+        c.is_ascii_alphanumeric()
+    }
+}
+
+impl SignatureEncoder for Base62 {
+    type TimestampProfile = Base64;
+
+    fn encoded_len(input_len: usize) -> usize {
+        // The smallest number of digits `d` where `62^d >= 256^input_len`, which is
+        // `ceil(input_len * 8 / log2(62))`. As `62^d` is never a power of two, the logarithm
+        // is never a whole number.
+        ((input_len as u64 * 8) << 32).div_ceil(LOG2_62) as usize
+    }
+
+    fn encode_str(input: &[u8], target: &mut String) {
+        with_scratch(Self::encoded_len(input.len()), |digits| {
+            encode_base62(input, digits);
+            target.push_str(ascii_str(digits));
+        })
+    }
+
+    fn encode_vec(input: &[u8], target: &mut Vec<u8>) {
+        let start = target.len();
+        target.resize(start + Self::encoded_len(input.len()), 0);
+        encode_base62(input, &mut target[start..]);
+    }
+
+    fn encode_slice(input: &[u8], target: &mut [u8]) -> usize {
+        let len = Self::encoded_len(input.len());
+        encode_base62(input, &mut target[..len]);
+        len
+    }
+
+    fn encode_fmt<W: ?Sized + fmt::Write>(input: &[u8], target: &mut W) -> fmt::Result {
+        with_scratch(Self::encoded_len(input.len()), |digits| {
+            encode_base62(input, digits);
+            target.write_str(ascii_str(digits))
+        })
+    }
+
+    fn decode<N: ArrayLength<u8>>(input: &[u8]) -> Option<GenericArray<u8, N>> {
+        if input.len() != Self::encoded_len(N::to_usize()) {
+            return None;
+        }
+
+        let mut output = GenericArray::<u8, N>::default();
+        for &c in input {
+            let mut carry = match c {
+                b'0'..=b'9' => c - b'0',
+                b'A'..=b'Z' => c - b'A' + 10,
+                b'a'..=b'z' => c - b'a' + 36,
+                _ => return None,
+            } as u32;
+            for byte in output.iter_mut().rev() {
+                let value = *byte as u32 * 62 + carry;
+                *byte = value as u8;
+                carry = value >> 8;
+            }
+            // The value does not fit in `N` bytes.
+            if carry != 0 {
+                return None;
+            }
+        }
+        Some(output)
+    }
+
+    fn in_alphabet(c: char) -> bool {
+        c.is_ascii_alphanumeric()
+    }
+}
+
+/// Encodes `input` using an alphabet of `2^bits` characters, most significant bits first,
+/// passing each character to `push`. The last character is padded with zero bits.
+#[inline(always)]
+fn encode_bits<F: FnMut(u8)>(input: &[u8], bits: u32, alphabet: &[u8], mut push: F) {
+    let mask = (1 << bits) - 1;
+    let mut buffer: u32 = 0;
+    let mut buffered = 0;
+    for &byte in input {
+        buffer = (buffer << 8) | byte as u32;
+        buffered += 8;
+        while buffered >= bits {
+            buffered -= bits;
+            push(alphabet[((buffer >> buffered) & mask) as usize]);
+        }
+    }
+    if buffered > 0 {
+        push(alphabet[((buffer << (bits - buffered)) & mask) as usize]);
+    }
+}
+
+/// Like [`encode_bits`], but writes the characters into the start of `target`, returning
+/// how many were written.
+#[inline(always)]
+fn encode_bits_slice(input: &[u8], bits: u32, alphabet: &[u8], target: &mut [u8]) -> usize {
+    let mut len = 0;
+    encode_bits(input, bits, alphabet, |c| {
+        target[len] = c;
+        len += 1;
+    });
+    len
+}
+
+/// Like [`encode_bits`], but writes the characters to `target`, stopping at the first error.
+#[inline(always)]
+fn encode_bits_fmt<W>(input: &[u8], bits: u32, alphabet: &[u8], target: &mut W) -> fmt::Result
+where
+    W: ?Sized + fmt::Write,
+{
+    let mut result = Ok(());
+    encode_bits(input, bits, alphabet, |c| {
+        if result.is_ok() {
+            result = target.write_char(c as char);
+        }
+    });
+    result
+}
+
+/// The inverse of [`encode_bits`], where `value` returns the value of a character. The
+/// padding bits must be zero, so that every signature has a single encoding (ignoring case).
+#[inline(always)]
+fn decode_bits<E, N, F>(input: &[u8], bits: u32, value: F) -> Option<GenericArray<u8, N>>
+where
+    E: SignatureEncoder,
+    N: ArrayLength<u8>,
+    F: Fn(u8) -> Option<u8>,
+{
+    if input.len() != E::encoded_len(N::to_usize()) {
+        return None;
+    }
+
+    let mut output = GenericArray::<u8, N>::default();
+    let mut buffer: u32 = 0;
+    let mut buffered = 0;
+    let mut index = 0;
+    for &c in input {
+        buffer = (buffer << bits) | value(c)? as u32;
+        buffered += bits;
+        if buffered >= 8 {
+            buffered -= 8;
+            output[index] = (buffer >> buffered) as u8;
+            index += 1;
+        }
+    }
+    if buffer & ((1 << buffered) - 1) != 0 {
+        return None;
+    }
+    Some(output)
+}
+
+/// Encodes `input` as a fixed width base62 number into `digits`, which must be exactly
+/// [`Base62::encoded_len`] bytes long.
+fn encode_base62(input: &[u8], digits: &mut [u8]) {
+    with_scratch(input.len(), |number| {
+        number.copy_from_slice(input);
+        // Repeatedly divide the number by 62, the remainder being the next least significant
+        // digit.
+        for digit in digits.iter_mut().rev() {
+            let mut remainder = 0;
+            for byte in number.iter_mut() {
+                let value = (remainder << 8) | *byte as u32;
+                *byte = (value / 62) as u8;
+                remainder = value % 62;
+            }
+            *digit = BASE62_ALPHABET[remainder as usize];
+        }
+    })
+}
+
+/// Calls `f` with a zeroed buffer of `len` bytes, which is on the stack unless it is larger
+/// than the encoded form of any built-in signature (a 64 byte signature as hex).
+fn with_scratch<R, F: FnOnce(&mut [u8]) -> R>(len: usize, f: F) -> R {
+    let mut stack = [0; 128];
+    if len <= stack.len() {
+        f(&mut stack[..len])
+    } else {
+        f(&mut vec![0; len])
+    }
+}
+
+/// Converts the output of an encoder, which is always ascii, to a `&str`.
+fn ascii_str(encoded: &[u8]) -> &str {
+    str::from_utf8(encoded).expect("encoded values are ascii")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use generic_array::typenum::{U0, U1, U20, U3, U32, U5};
+
+    fn encode<E: SignatureEncoder>(input: &[u8]) -> String {
+        let mut output = String::new();
+        E::encode_str(input, &mut output);
+        let mut output_vec = Vec::new();
+        E::encode_vec(input, &mut output_vec);
+        assert_eq!(output.as_bytes(), output_vec.as_slice());
+        assert_eq!(output.len(), E::encoded_len(input.len()));
+
+        let mut output_slice = vec![0xff; output.len() + 1];
+        assert_eq!(E::encode_slice(input, &mut output_slice), output.len());
+        assert_eq!(&output_slice[..output.len()], output.as_bytes());
+        let mut output_fmt = String::from("prefix");
+        E::encode_fmt(input, &mut output_fmt).unwrap();
+        assert_eq!(output_fmt, format!("prefix{}", output));
+        output
+    }
+
+    fn check_round_trip<E: SignatureEncoder>() {
+        let input: GenericArray<u8, U32> = GenericArray::clone_from_slice(&[0xa5; 32]);
+        let encoded = encode::<E>(&input);
+        assert_eq!(E::decode::<U32>(encoded.as_bytes()), Some(input));
+        assert_eq!(E::decode::<U20>(encoded.as_bytes()), None);
+
+        for input in &[[0u8; 20], [0xff; 20]] {
+            let encoded = encode::<E>(input);
+            assert_eq!(
+                E::decode::<U20>(encoded.as_bytes()).unwrap().as_slice(),
+                input
+            );
+            assert!(encoded.chars().all(E::in_alphabet));
+        }
+
+        assert_eq!(encode::<E>(&[]), "");
+        assert!(E::decode::<U0>(b"").unwrap().is_empty());
+        assert_eq!(E::decode::<U1>(b"!!"), None);
+    }
+
+    #[test]
+    fn test_base64() {
+        check_round_trip::<Base64>();
+        assert_eq!(encode::<Base64>(b"\xfb\xff"), "-_8");
+        assert!(Base64::in_alphabet('-'));
+        assert!(!Base64::in_alphabet('.'));
+
+        // Canonical padding is accepted, but not required.
+        assert_eq!(Base64::decode::<U1>(b"-w=="), Some([0xfb].into()));
+        assert_eq!(Base64::decode::<U1>(b"-w"), Some([0xfb].into()));
+        assert_eq!(Base64::decode::<U1>(b"-w="), None);
+        assert_eq!(Base64::decode::<U1>(b"-w==="), None);
+    }
+
+    #[test]
+    fn test_base64_profiles() {
+        check_round_trip::<StandardBase64>();
+        assert_eq!(encode::<StandardBase64>(b"\xfb\xff"), "+/8=");
+        assert_eq!(StandardBase64::decode::<U1>(b"+w=="), Some([0xfb].into()));
+        assert_eq!(StandardBase64::decode::<U1>(b"+w"), None);
+        assert_eq!(StandardBase64::decode::<U1>(b"-w=="), None);
+        assert!(StandardBase64::in_alphabet('+'));
+        assert!(StandardBase64::in_alphabet('='));
+        assert!(!StandardBase64::in_alphabet('-'));
+
+        check_round_trip::<UrlSafePaddedBase64>();
+        assert_eq!(encode::<UrlSafePaddedBase64>(b"\xfb\xff"), "-_8=");
+        assert_eq!(UrlSafePaddedBase64::decode::<U1>(b"-w"), None);
+
+        struct Unpadded;
+
+        impl Base64Profile for Unpadded {
+            const ALPHABET: Base64Alphabet = Base64Alphabet::Standard;
+            const PAD: bool = false;
+            const DECODE_PADDING: DecodePadding = DecodePadding::Forbidden;
+        }
+
+        check_round_trip::<Unpadded>();
+        assert_eq!(encode::<Unpadded>(b"\xfb\xff"), "+/8");
+        assert_eq!(Unpadded::decode::<U1>(b"+w"), Some([0xfb].into()));
+        assert_eq!(Unpadded::decode::<U1>(b"+w=="), None);
+        assert!(!Unpadded::in_alphabet('='));
+    }
+
+    #[test]
+    fn test_hex() {
+        check_round_trip::<Hex>();
+        assert_eq!(encode::<Hex>(b"\x01\xab\xff"), "01abff");
+        assert_eq!(&Hex::decode::<U3>(b"01ABff").unwrap()[..], b"\x01\xab\xff");
+        assert_eq!(Hex::decode::<U3>(b"01abfg"), None);
+        assert!(!Hex::in_alphabet('g'));
+        assert!(!Hex::in_alphabet('-'));
+    }
+
+    #[test]
+    fn test_crockford_base32() {
+        check_round_trip::<CrockfordBase32>();
+        assert_eq!(encode::<CrockfordBase32>(b"hello"), "D1JPRV3F");
+        assert_eq!(
+            &CrockfordBase32::decode::<U5>(b"d1jprv3f").unwrap()[..],
+            b"hello"
+        );
+        assert_eq!(encode::<CrockfordBase32>(b"\x00\x10"), "0080");
+        assert_eq!(
+            CrockfordBase32::decode::<U3>(b"oIL0G"),
+            CrockfordBase32::decode::<U3>(b"0110G")
+        );
+        // The padding bits must be zero.
+        assert_eq!(CrockfordBase32::decode::<U1>(b"01"), None);
+        assert_eq!(CrockfordBase32::decode::<U1>(b"0U"), None);
+        assert!(!CrockfordBase32::in_alphabet('-'));
+    }
+
+    #[test]
+    fn test_base62() {
+        check_round_trip::<Base62>();
+        assert_eq!(Base62::encoded_len(1), 2);
+        assert_eq!(Base62::encoded_len(20), 27);
+        assert_eq!(Base62::encoded_len(32), 43);
+        for input_len in 0..=512 {
+            let expected = (input_len as f64 * 8.0 / 62f64.log2()).ceil() as usize;
+            assert_eq!(Base62::encoded_len(input_len), expected, "{}", input_len);
+        }
+        assert_eq!(encode::<Base62>(b"\xff"), "47");
+        assert_eq!(encode::<Base62>(b"\x00\x00\x3e"), "00010");
+        assert_eq!(&Base62::decode::<U1>(b"47").unwrap()[..], b"\xff");
+        // 62 * 62 - 1 does not fit in a single byte.
+        assert_eq!(Base62::decode::<U1>(b"zz"), None);
+        assert_eq!(Base62::decode::<U1>(b"4-"), None);
+        assert!(!Base62::in_alphabet('-'));
+        assert!(!Base62::in_alphabet('_'));
+    }
+}
@@ -1,9 +1,22 @@
-use std::time::{Duration, SystemTime};
-use std::{error, fmt, str};
+use crate::time::{Duration, SystemTime};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::{error, fmt, str};
 
 use crate::base64;
 use crate::Separator;
+use hmac::digest::Digest;
 
+/// Errors that can occur while decoding and deserializing the payload of a serializer.
+///
+/// # Redaction
+/// Like the errors that contain it, the [`Display`] output of this error only describes the
+/// kind of error. Its [`source`] is the underlying error, except for JSON errors, whose
+/// `Display` output quotes the payload. Those are wrapped in a [`RedactedJsonError`], so the
+/// whole chain of sources is safe to log.
+///
+/// [`Display`]: fmt::Display
+/// [`source`]: error::Error::source
 #[derive(Debug)]
 pub enum PayloadError {
     #[cfg(feature = "serializer")]
@@ -18,39 +31,256 @@ pub struct SeparatorNotFound {
 }
 
 /// Errors that can occur while unsigning a "signed value".
+///
+/// `V` is the type of the value that was being unsigned, which is [`prim@str`] for
+/// [`Signer::unsign`] and `[u8]` for [`Signer::unsign_bytes`].
+///
+/// # Redaction
+/// Signed values are often bearer credentials, so the [`Display`] output of this error never
+/// contains the signature or the value. They are shown as their length and a short
+/// fingerprint instead, which can be used to correlate errors with a token. The full details
+/// are available through [`unredacted`] and the [`Debug`] output.
+///
+/// ```rust
+/// use itsdangerous::{default_builder, Signer};
+///
+/// let signer = default_builder("secret key").build();
+/// let error = signer.unsign("hello.tampered").unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Signature (8 bytes, fingerprint 93d6c93d) does not match."
+/// );
+/// assert_eq!(
+///     error.unredacted().to_string(),
+///     "Signature \"tampered\" does not match."
+/// );
+/// ```
+///
+/// [`Signer::unsign`]: crate::Signer::unsign
+/// [`Signer::unsign_bytes`]: crate::Signer::unsign_bytes
+/// [`Display`]: fmt::Display
+/// [`Debug`]: fmt::Debug
+/// [`unredacted`]: BadSignature::unredacted
 #[derive(Debug)]
-pub enum BadSignature<'a> {
+pub enum BadSignature<'a, V: ?Sized = str> {
     /// A string was provided to unsign, but it did not contain
     /// the expected separator.
     SeparatorNotFound { separator: Separator },
     /// The signature did not match what we expected it to be.
-    SignatureMismatch { signature: &'a str, value: &'a str },
+    SignatureMismatch { signature: &'a V, value: &'a V },
     /// The payload is invalid, e.g. it cannot be parsed.
-    PayloadInvalid { value: &'a str, error: PayloadError },
+    PayloadInvalid { value: &'a V, error: PayloadError },
 }
 
 /// Errors that can occur while unsigning a "signed value" using the timed signer.
+///
+/// `V` is the type of the value that was being unsigned, which is [`prim@str`] for
+/// [`TimestampSigner::unsign`] and `[u8]` for [`TimestampSigner::unsign_bytes`].
+///
+/// Like [`BadSignature`], the [`Display`] output of this error is redacted, see
+/// [`unredacted`].
+///
+/// [`Display`]: fmt::Display
+/// [`unredacted`]: BadTimedSignature::unredacted
+/// [`TimestampSigner::unsign`]: crate::TimestampSigner::unsign
+/// [`TimestampSigner::unsign_bytes`]: crate::TimestampSigner::unsign_bytes
 #[derive(Debug)]
-pub enum BadTimedSignature<'a> {
+#[non_exhaustive]
+pub enum BadTimedSignature<'a, V: ?Sized = str> {
     /// A string was provided to unsign, but it did not contain
     /// the expected separator.
     SeparatorNotFound { separator: Separator },
     /// The signature did not match what we expected it to be.
-    SignatureMismatch { signature: &'a str, value: &'a str },
+    SignatureMismatch { signature: &'a V, value: &'a V },
     /// The payload is invalid, e.g. it cannot be parsed.
-    PayloadInvalid { value: &'a str, error: PayloadError },
+    PayloadInvalid { value: &'a V, error: PayloadError },
     /// The timestamp is missing, but the value was signed with a correct
     /// secret key + salt.
-    TimestampMissing { value: &'a str },
+    TimestampMissing { value: &'a V },
     /// The timestamp was present and signed, but we weren't able to parse it back to
     /// a SystemTime.
-    TimestampInvalid { timestamp: &'a str },
+    TimestampInvalid { timestamp: &'a V },
     /// The timestamp expired - meaning that it was more than `max_age` ago.
     TimestampExpired {
         timestamp: SystemTime,
         max_age: Duration,
-        value: &'a str,
+        value: &'a V,
     },
+    /// The timestamp is too far in the future - meaning that it was less than `min_age` ago,
+    /// allowing for `max_future_skew`. See [`ExpiryPolicy`].
+    ///
+    /// [`ExpiryPolicy`]: crate::ExpiryPolicy
+    TimestampInFuture {
+        timestamp: SystemTime,
+        min_age: Duration,
+        max_future_skew: Duration,
+        value: &'a V,
+    },
+    /// The token expired - meaning that the `expires_at` it was signed with has passed. See
+    /// [`TimestampSigner::sign_with_expiry`].
+    ///
+    /// [`TimestampSigner::sign_with_expiry`]: crate::TimestampSigner::sign_with_expiry
+    TokenExpired {
+        expires_at: SystemTime,
+        value: &'a V,
+    },
+    /// The token is not valid yet - meaning that the `not_before` it was signed with has not
+    /// passed. See [`TimestampSigner::sign_with_validity`].
+    ///
+    /// [`TimestampSigner::sign_with_validity`]: crate::TimestampSigner::sign_with_validity
+    TokenNotYetValid {
+        not_before: SystemTime,
+        value: &'a V,
+    },
+}
+
+/// An owned version of [`BadSignature`], which does not borrow from the value that was being
+/// unsigned, and can therefore be returned from async handlers or boxed into a
+/// `Box<dyn Error + Send + Sync>`. Created using [`BadSignature::into_owned`], or by `?`.
+///
+/// `V` is the owned type of the value that was being unsigned, which is [`String`] for
+/// [`Signer::unsign`] and [`Vec<u8>`] for [`Signer::unsign_bytes`].
+///
+/// # Example
+/// ```rust
+/// use itsdangerous::{default_builder, OwnedBadSignature, Signer};
+///
+/// fn unsign_token(token: String) -> Result<String, OwnedBadSignature> {
+///     let signer = default_builder("secret key").build();
+///     Ok(signer.unsign(&token)?.to_owned())
+/// }
+///
+/// let error = unsign_token("hello.tampered".to_owned()).unwrap_err();
+/// let error: Box<dyn std::error::Error + Send + Sync> = Box::new(error);
+/// ```
+///
+/// [`Signer::unsign`]: crate::Signer::unsign
+/// [`Signer::unsign_bytes`]: crate::Signer::unsign_bytes
+/// [`Vec<u8>`]: alloc::vec::Vec
+#[derive(Debug)]
+pub enum OwnedBadSignature<V = String> {
+    /// See [`BadSignature::SeparatorNotFound`].
+    SeparatorNotFound { separator: Separator },
+    /// See [`BadSignature::SignatureMismatch`].
+    SignatureMismatch { signature: V, value: V },
+    /// See [`BadSignature::PayloadInvalid`].
+    PayloadInvalid { value: V, error: PayloadError },
+}
+
+/// An owned version of [`BadTimedSignature`], created using [`BadTimedSignature::into_owned`],
+/// or by `?`. See [`OwnedBadSignature`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum OwnedBadTimedSignature<V = String> {
+    /// See [`BadTimedSignature::SeparatorNotFound`].
+    SeparatorNotFound { separator: Separator },
+    /// See [`BadTimedSignature::SignatureMismatch`].
+    SignatureMismatch { signature: V, value: V },
+    /// See [`BadTimedSignature::PayloadInvalid`].
+    PayloadInvalid { value: V, error: PayloadError },
+    /// See [`BadTimedSignature::TimestampMissing`].
+    TimestampMissing { value: V },
+    /// See [`BadTimedSignature::TimestampInvalid`].
+    TimestampInvalid { timestamp: V },
+    /// See [`BadTimedSignature::TimestampExpired`].
+    TimestampExpired {
+        timestamp: SystemTime,
+        max_age: Duration,
+        value: V,
+    },
+    /// See [`BadTimedSignature::TimestampInFuture`].
+    TimestampInFuture {
+        timestamp: SystemTime,
+        min_age: Duration,
+        max_future_skew: Duration,
+        value: V,
+    },
+    /// See [`BadTimedSignature::TokenExpired`].
+    TokenExpired { expires_at: SystemTime, value: V },
+    /// See [`BadTimedSignature::TokenNotYetValid`].
+    TokenNotYetValid { not_before: SystemTime, value: V },
+}
+
+/// Displays an error including the full signature, value or timestamp that it refers to,
+/// which the [`Display`] output of the error itself redacts. Created using `unredacted()`,
+/// e.g. [`BadSignature::unredacted`].
+///
+/// [`Display`]: fmt::Display
+#[derive(Debug)]
+pub struct Unredacted<'e, E>(&'e E);
+
+/// Displays a part of a signed value either in full, or as its length and a short fingerprint,
+/// which is the first 4 bytes of its SHA-1 digest. The fingerprint can be used to correlate
+/// errors with a given token, without revealing the token.
+struct Sensitive<'v, V: ?Sized> {
+    value: &'v V,
+    unredacted: bool,
+}
+
+impl<'v, V: ?Sized> Sensitive<'v, V> {
+    fn new(value: &'v V, unredacted: bool) -> Self {
+        Self { value, unredacted }
+    }
+}
+
+impl<'v, V: ?Sized + fmt::Debug + AsRef<[u8]>> fmt::Display for Sensitive<'v, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.unredacted {
+            return write!(f, "{:?}", self.value);
+        }
+
+        let value = self.value.as_ref();
+        write!(f, "({} bytes, fingerprint ", value.len())?;
+        for byte in &sha1::Sha1::digest(value)[..4] {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, ")")
+    }
+}
+
+fn display_payload_invalid<V: ?Sized + fmt::Debug + AsRef<[u8]>>(
+    f: &mut fmt::Formatter,
+    value: &V,
+    error: &PayloadError,
+    unredacted: bool,
+) -> fmt::Result {
+    if unredacted {
+        write!(
+            f,
+            "Payload {:?} cannot be parsed because {:?}.",
+            value, error
+        )
+    } else {
+        // The errors of the payload formats can quote the payload, so only their kind is
+        // shown. `PayloadError::source` redacts them as well.
+        write!(
+            f,
+            "Payload {} cannot be parsed because it is {}.",
+            Sensitive::new(value, false),
+            error.reason()
+        )
+    }
+}
+
+fn display_in_future(
+    f: &mut fmt::Formatter,
+    timestamp: SystemTime,
+    min_age: Duration,
+    max_future_skew: Duration,
+) -> fmt::Result {
+    if min_age == Duration::from_secs(0) {
+        write!(
+            f,
+            "Timestamp {:?} is more than {:?} in the future.",
+            timestamp, max_future_skew
+        )
+    } else {
+        write!(
+            f,
+            "Timestamp {:?} is less than {:?} old, allowing for {:?} of clock skew.",
+            timestamp, min_age, max_future_skew
+        )
+    }
 }
 
 pub struct TimestampExpired<T> {
@@ -59,28 +289,83 @@ pub struct TimestampExpired<T> {
     pub value: T,
 }
 
-/// Error that occurs when trying to construct a Separator with
-/// a char is in the base64 url-safe alphabet.
+/// Error that occurs when trying to construct a Separator that is empty, or
+/// that contains a char in the alphabet of the signature encoder.
 #[derive(Debug)]
-pub struct InvalidSeparator(pub char);
+pub struct InvalidSeparator(pub String);
 
-impl<'a> fmt::Display for BadSignature<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+/// Error that occurs when building a signer using [`RuntimeSignerBuilder`] with an
+/// algorithm name that is not known, or whose cargo feature is not enabled.
+///
+/// [`RuntimeSignerBuilder`]: crate::RuntimeSignerBuilder
+#[derive(Debug)]
+pub enum UnknownAlgorithm {
+    /// The signing algorithm (e.g. `"hmac-sha1"`) is not known.
+    SigningAlgorithm(String),
+    /// The key derivation (e.g. `"django-concat"`) is not known.
+    KeyDerivation(String),
+}
+
+/// Error that occurs when a key is not valid for an asymmetric signing algorithm.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InvalidKey {
+    /// The private key at the given index is not valid.
+    PrivateKey(usize),
+    /// The public key at the given index is not valid.
+    PublicKey(usize),
+}
+
+/// Error that occurs when signing into a buffer that is too small to hold the signed value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    /// The number of bytes that the signed value requires.
+    pub required: usize,
+    /// The number of bytes that the buffer has.
+    pub available: usize,
+}
+
+impl<'a, V: ?Sized + fmt::Debug + AsRef<[u8]>> BadSignature<'a, V> {
+    /// Returns a wrapper whose [`Display`] output includes the full signature and value,
+    /// unlike the [`Display`] output of this error. Signed values are often bearer
+    /// credentials, so this should not be logged.
+    ///
+    /// [`Display`]: fmt::Display
+    pub fn unredacted(&self) -> Unredacted<'_, Self> {
+        Unredacted(self)
+    }
+
+    fn display(&self, f: &mut fmt::Formatter, unredacted: bool) -> fmt::Result {
         match self {
             BadSignature::SeparatorNotFound { separator } => {
                 write!(f, "Separator {:?} not found in value.", separator.0)
             }
-            BadSignature::SignatureMismatch { signature, .. } => {
-                write!(f, "Signature {:?} does not match.", signature)
-            }
-            BadSignature::PayloadInvalid { error, .. } => {
-                write!(f, "Payload cannot be parsed because {:?}.", error)
+            BadSignature::SignatureMismatch { signature, .. } => write!(
+                f,
+                "Signature {} does not match.",
+                Sensitive::new(*signature, unredacted)
+            ),
+            BadSignature::PayloadInvalid { value, error } => {
+                display_payload_invalid(f, *value, error, unredacted)
             }
         }
     }
 }
 
-impl<'a> error::Error for BadSignature<'a> {
+impl<'a, V: ?Sized + fmt::Debug + AsRef<[u8]>> fmt::Display for BadSignature<'a, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(f, false)
+    }
+}
+
+impl<'e, 'a, V: ?Sized + fmt::Debug + AsRef<[u8]>> fmt::Display
+    for Unredacted<'e, BadSignature<'a, V>>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.display(f, true)
+    }
+}
+
+impl<'a, V: ?Sized + fmt::Debug + AsRef<[u8]>> error::Error for BadSignature<'a, V> {
     fn description(&self) -> &str {
         match *self {
             BadSignature::SeparatorNotFound { .. } => "separator not found",
@@ -89,27 +374,42 @@ impl<'a> error::Error for BadSignature<'a> {
         }
     }
 
-    fn cause(&self) -> Option<&dyn error::Error> {
-        None
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            BadSignature::PayloadInvalid { error, .. } => Some(error),
+            _ => None,
+        }
     }
 }
 
-impl<'a> fmt::Display for BadTimedSignature<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl<'a, V: ?Sized + fmt::Debug + AsRef<[u8]>> BadTimedSignature<'a, V> {
+    /// Returns a wrapper whose [`Display`] output includes the full signature, value and
+    /// timestamp. See [`BadSignature::unredacted`].
+    ///
+    /// [`Display`]: fmt::Display
+    pub fn unredacted(&self) -> Unredacted<'_, Self> {
+        Unredacted(self)
+    }
+
+    fn display(&self, f: &mut fmt::Formatter, unredacted: bool) -> fmt::Result {
         match self {
             BadTimedSignature::SeparatorNotFound { separator, .. } => {
                 write!(f, "Separator {:?} not found in value.", separator.0)
             }
-            BadTimedSignature::SignatureMismatch { signature, .. } => {
-                write!(f, "Signature {:?} does not match.", signature)
-            }
-            BadTimedSignature::PayloadInvalid { error, .. } => {
-                write!(f, "Payload cannot be parsed because {:?}.", error)
+            BadTimedSignature::SignatureMismatch { signature, .. } => write!(
+                f,
+                "Signature {} does not match.",
+                Sensitive::new(*signature, unredacted)
+            ),
+            BadTimedSignature::PayloadInvalid { value, error } => {
+                display_payload_invalid(f, *value, error, unredacted)
             }
             BadTimedSignature::TimestampMissing { .. } => write!(f, "Timestamp missing"),
-            BadTimedSignature::TimestampInvalid { timestamp } => {
-                write!(f, "Timestamp {:?} is invalid", timestamp)
-            }
+            BadTimedSignature::TimestampInvalid { timestamp } => write!(
+                f,
+                "Timestamp {} is invalid",
+                Sensitive::new(*timestamp, unredacted)
+            ),
             BadTimedSignature::TimestampExpired {
                 timestamp, max_age, ..
             } => write!(
@@ -117,11 +417,37 @@ impl<'a> fmt::Display for BadTimedSignature<'a> {
                 "Timestamp {:?} is older than {:?} and is expired.",
                 timestamp, max_age
             ),
+            BadTimedSignature::TimestampInFuture {
+                timestamp,
+                min_age,
+                max_future_skew,
+                ..
+            } => display_in_future(f, *timestamp, *min_age, *max_future_skew),
+            BadTimedSignature::TokenExpired { expires_at, .. } => {
+                write!(f, "Token expired at {:?}.", expires_at)
+            }
+            BadTimedSignature::TokenNotYetValid { not_before, .. } => {
+                write!(f, "Token is not valid before {:?}.", not_before)
+            }
         }
     }
 }
 
-impl<'a> error::Error for BadTimedSignature<'a> {
+impl<'a, V: ?Sized + fmt::Debug + AsRef<[u8]>> fmt::Display for BadTimedSignature<'a, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(f, false)
+    }
+}
+
+impl<'e, 'a, V: ?Sized + fmt::Debug + AsRef<[u8]>> fmt::Display
+    for Unredacted<'e, BadTimedSignature<'a, V>>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.display(f, true)
+    }
+}
+
+impl<'a, V: ?Sized + fmt::Debug + AsRef<[u8]>> error::Error for BadTimedSignature<'a, V> {
     fn description(&self) -> &str {
         match *self {
             BadTimedSignature::SeparatorNotFound { .. } => "separator not found",
@@ -129,17 +455,474 @@ impl<'a> error::Error for BadTimedSignature<'a> {
             BadTimedSignature::TimestampMissing { .. } => "timestamp missing",
             BadTimedSignature::TimestampInvalid { .. } => "timestamp invalid",
             BadTimedSignature::TimestampExpired { .. } => "timestamp expired",
+            BadTimedSignature::TimestampInFuture { .. } => "timestamp in future",
+            BadTimedSignature::TokenExpired { .. } => "token expired",
+            BadTimedSignature::TokenNotYetValid { .. } => "token not yet valid",
             BadTimedSignature::PayloadInvalid { .. } => "payload invalid",
         }
     }
 
-    fn cause(&self) -> Option<&dyn error::Error> {
-        None
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            BadTimedSignature::PayloadInvalid { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, V: ?Sized> BadSignature<'a, V> {
+    /// Returns whether the signature does not match the value, meaning that either was
+    /// tampered with, or that it was signed with a different key or salt. This usually maps
+    /// to HTTP 401.
+    pub fn is_tampered(&self) -> bool {
+        matches!(self, BadSignature::SignatureMismatch { .. })
+    }
+
+    /// Returns whether the value was signed correctly, but has expired. This is always `false`,
+    /// as values are only signed with a timestamp by a [`TimestampSigner`].
+    ///
+    /// [`TimestampSigner`]: crate::TimestampSigner
+    pub fn is_expired(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the value could not be parsed, even though it may have been signed
+    /// correctly. This usually maps to HTTP 400.
+    pub fn is_malformed(&self) -> bool {
+        matches!(
+            self,
+            BadSignature::SeparatorNotFound { .. } | BadSignature::PayloadInvalid { .. }
+        )
+    }
+
+    /// Returns a stable, machine readable code for the kind of error, which is one of:
+    /// - `separator_not_found`
+    /// - `signature_mismatch`
+    /// - `payload_invalid`
+    pub fn code(&self) -> &'static str {
+        match self {
+            BadSignature::SeparatorNotFound { .. } => "separator_not_found",
+            BadSignature::SignatureMismatch { .. } => "signature_mismatch",
+            BadSignature::PayloadInvalid { .. } => "payload_invalid",
+        }
+    }
+}
+
+impl<'a, V: ?Sized> BadTimedSignature<'a, V> {
+    /// Returns whether the signature does not match the value, meaning that either was
+    /// tampered with, or that it was signed with a different key or salt. This usually maps
+    /// to HTTP 401.
+    pub fn is_tampered(&self) -> bool {
+        matches!(self, BadTimedSignature::SignatureMismatch { .. })
+    }
+
+    /// Returns whether the value was signed correctly, but its timestamp is too old, or the
+    /// token has expired. This usually maps to HTTP 410.
+    pub fn is_expired(&self) -> bool {
+        matches!(
+            self,
+            BadTimedSignature::TimestampExpired { .. } | BadTimedSignature::TokenExpired { .. }
+        )
+    }
+
+    /// Returns whether the value was signed correctly, but its timestamp is too far in the
+    /// future or too recent, see [`ExpiryPolicy`], or the token is not valid yet.
+    ///
+    /// [`ExpiryPolicy`]: crate::ExpiryPolicy
+    pub fn is_in_future(&self) -> bool {
+        matches!(
+            self,
+            BadTimedSignature::TimestampInFuture { .. }
+                | BadTimedSignature::TokenNotYetValid { .. }
+        )
+    }
+
+    /// Returns whether the value could not be parsed, even though it may have been signed
+    /// correctly. This usually maps to HTTP 400.
+    pub fn is_malformed(&self) -> bool {
+        matches!(
+            self,
+            BadTimedSignature::SeparatorNotFound { .. }
+                | BadTimedSignature::PayloadInvalid { .. }
+                | BadTimedSignature::TimestampMissing { .. }
+                | BadTimedSignature::TimestampInvalid { .. }
+        )
+    }
+
+    /// Returns a stable, machine readable code for the kind of error, which is one of:
+    /// - `separator_not_found`
+    /// - `signature_mismatch`
+    /// - `payload_invalid`
+    /// - `timestamp_missing`
+    /// - `timestamp_invalid`
+    /// - `timestamp_expired`
+    /// - `timestamp_in_future`
+    /// - `token_expired`
+    /// - `token_not_yet_valid`
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::{default_builder, IntoTimestampSigner, TimestampSigner};
+    ///
+    /// let signer = default_builder("secret key").build().into_timestamp_signer();
+    /// let error = signer.unsign("hello world.tampered").err().unwrap();
+    /// let status = if error.is_malformed() {
+    ///     400
+    /// } else if error.is_expired() {
+    ///     410
+    /// } else {
+    ///     401
+    /// };
+    /// assert_eq!((status, error.code()), (401, "signature_mismatch"));
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            BadTimedSignature::SeparatorNotFound { .. } => "separator_not_found",
+            BadTimedSignature::SignatureMismatch { .. } => "signature_mismatch",
+            BadTimedSignature::PayloadInvalid { .. } => "payload_invalid",
+            BadTimedSignature::TimestampMissing { .. } => "timestamp_missing",
+            BadTimedSignature::TimestampInvalid { .. } => "timestamp_invalid",
+            BadTimedSignature::TimestampExpired { .. } => "timestamp_expired",
+            BadTimedSignature::TimestampInFuture { .. } => "timestamp_in_future",
+            BadTimedSignature::TokenExpired { .. } => "token_expired",
+            BadTimedSignature::TokenNotYetValid { .. } => "token_not_yet_valid",
+        }
     }
 }
 
-impl<'a> From<BadSignature<'a>> for BadTimedSignature<'a> {
-    fn from(bad_signature: BadSignature<'a>) -> Self {
+impl<V> OwnedBadSignature<V> {
+    /// See [`BadSignature::is_tampered`].
+    pub fn is_tampered(&self) -> bool {
+        matches!(self, OwnedBadSignature::SignatureMismatch { .. })
+    }
+
+    /// See [`BadSignature::is_expired`].
+    pub fn is_expired(&self) -> bool {
+        false
+    }
+
+    /// See [`BadSignature::is_malformed`].
+    pub fn is_malformed(&self) -> bool {
+        matches!(
+            self,
+            OwnedBadSignature::SeparatorNotFound { .. } | OwnedBadSignature::PayloadInvalid { .. }
+        )
+    }
+
+    /// See [`BadSignature::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            OwnedBadSignature::SeparatorNotFound { .. } => "separator_not_found",
+            OwnedBadSignature::SignatureMismatch { .. } => "signature_mismatch",
+            OwnedBadSignature::PayloadInvalid { .. } => "payload_invalid",
+        }
+    }
+}
+
+impl<V> OwnedBadTimedSignature<V> {
+    /// See [`BadTimedSignature::is_tampered`].
+    pub fn is_tampered(&self) -> bool {
+        matches!(self, OwnedBadTimedSignature::SignatureMismatch { .. })
+    }
+
+    /// See [`BadTimedSignature::is_expired`].
+    pub fn is_expired(&self) -> bool {
+        matches!(
+            self,
+            OwnedBadTimedSignature::TimestampExpired { .. }
+                | OwnedBadTimedSignature::TokenExpired { .. }
+        )
+    }
+
+    /// See [`BadTimedSignature::is_in_future`].
+    pub fn is_in_future(&self) -> bool {
+        matches!(
+            self,
+            OwnedBadTimedSignature::TimestampInFuture { .. }
+                | OwnedBadTimedSignature::TokenNotYetValid { .. }
+        )
+    }
+
+    /// See [`BadTimedSignature::is_malformed`].
+    pub fn is_malformed(&self) -> bool {
+        matches!(
+            self,
+            OwnedBadTimedSignature::SeparatorNotFound { .. }
+                | OwnedBadTimedSignature::PayloadInvalid { .. }
+                | OwnedBadTimedSignature::TimestampMissing { .. }
+                | OwnedBadTimedSignature::TimestampInvalid { .. }
+        )
+    }
+
+    /// See [`BadTimedSignature::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            OwnedBadTimedSignature::SeparatorNotFound { .. } => "separator_not_found",
+            OwnedBadTimedSignature::SignatureMismatch { .. } => "signature_mismatch",
+            OwnedBadTimedSignature::PayloadInvalid { .. } => "payload_invalid",
+            OwnedBadTimedSignature::TimestampMissing { .. } => "timestamp_missing",
+            OwnedBadTimedSignature::TimestampInvalid { .. } => "timestamp_invalid",
+            OwnedBadTimedSignature::TimestampExpired { .. } => "timestamp_expired",
+            OwnedBadTimedSignature::TimestampInFuture { .. } => "timestamp_in_future",
+            OwnedBadTimedSignature::TokenExpired { .. } => "token_expired",
+            OwnedBadTimedSignature::TokenNotYetValid { .. } => "token_not_yet_valid",
+        }
+    }
+}
+
+impl<'a, V: ?Sized + ToOwned> BadSignature<'a, V> {
+    /// Copies the borrowed parts of this error, so that it no longer borrows from the value
+    /// that was being unsigned.
+    pub fn into_owned(self) -> OwnedBadSignature<V::Owned> {
+        match self {
+            BadSignature::SeparatorNotFound { separator } => {
+                OwnedBadSignature::SeparatorNotFound { separator }
+            }
+            BadSignature::SignatureMismatch { signature, value } => {
+                OwnedBadSignature::SignatureMismatch {
+                    signature: signature.to_owned(),
+                    value: value.to_owned(),
+                }
+            }
+            BadSignature::PayloadInvalid { value, error } => OwnedBadSignature::PayloadInvalid {
+                value: value.to_owned(),
+                error,
+            },
+        }
+    }
+}
+
+impl<'a, V: ?Sized + ToOwned> BadTimedSignature<'a, V> {
+    /// Copies the borrowed parts of this error, so that it no longer borrows from the value
+    /// that was being unsigned.
+    pub fn into_owned(self) -> OwnedBadTimedSignature<V::Owned> {
+        match self {
+            BadTimedSignature::SeparatorNotFound { separator } => {
+                OwnedBadTimedSignature::SeparatorNotFound { separator }
+            }
+            BadTimedSignature::SignatureMismatch { signature, value } => {
+                OwnedBadTimedSignature::SignatureMismatch {
+                    signature: signature.to_owned(),
+                    value: value.to_owned(),
+                }
+            }
+            BadTimedSignature::PayloadInvalid { value, error } => {
+                OwnedBadTimedSignature::PayloadInvalid {
+                    value: value.to_owned(),
+                    error,
+                }
+            }
+            BadTimedSignature::TimestampMissing { value } => {
+                OwnedBadTimedSignature::TimestampMissing {
+                    value: value.to_owned(),
+                }
+            }
+            BadTimedSignature::TimestampInvalid { timestamp } => {
+                OwnedBadTimedSignature::TimestampInvalid {
+                    timestamp: timestamp.to_owned(),
+                }
+            }
+            BadTimedSignature::TimestampExpired {
+                timestamp,
+                max_age,
+                value,
+            } => OwnedBadTimedSignature::TimestampExpired {
+                timestamp,
+                max_age,
+                value: value.to_owned(),
+            },
+            BadTimedSignature::TimestampInFuture {
+                timestamp,
+                min_age,
+                max_future_skew,
+                value,
+            } => OwnedBadTimedSignature::TimestampInFuture {
+                timestamp,
+                min_age,
+                max_future_skew,
+                value: value.to_owned(),
+            },
+            BadTimedSignature::TokenExpired { expires_at, value } => {
+                OwnedBadTimedSignature::TokenExpired {
+                    expires_at,
+                    value: value.to_owned(),
+                }
+            }
+            BadTimedSignature::TokenNotYetValid { not_before, value } => {
+                OwnedBadTimedSignature::TokenNotYetValid {
+                    not_before,
+                    value: value.to_owned(),
+                }
+            }
+        }
+    }
+}
+
+impl<'a, V: ?Sized + ToOwned> From<BadSignature<'a, V>> for OwnedBadSignature<V::Owned> {
+    fn from(bad_signature: BadSignature<'a, V>) -> Self {
+        bad_signature.into_owned()
+    }
+}
+
+impl<'a, V: ?Sized + ToOwned> From<BadTimedSignature<'a, V>> for OwnedBadTimedSignature<V::Owned> {
+    fn from(bad_signature: BadTimedSignature<'a, V>) -> Self {
+        bad_signature.into_owned()
+    }
+}
+
+impl<V: fmt::Debug + AsRef<[u8]>> OwnedBadSignature<V> {
+    /// See [`BadSignature::unredacted`].
+    pub fn unredacted(&self) -> Unredacted<'_, Self> {
+        Unredacted(self)
+    }
+
+    fn display(&self, f: &mut fmt::Formatter, unredacted: bool) -> fmt::Result {
+        match self {
+            OwnedBadSignature::SeparatorNotFound { separator } => {
+                write!(f, "Separator {:?} not found in value.", separator.0)
+            }
+            OwnedBadSignature::SignatureMismatch { signature, .. } => write!(
+                f,
+                "Signature {} does not match.",
+                Sensitive::new(signature, unredacted)
+            ),
+            OwnedBadSignature::PayloadInvalid { value, error } => {
+                display_payload_invalid(f, value, error, unredacted)
+            }
+        }
+    }
+}
+
+impl<V: fmt::Debug + AsRef<[u8]>> fmt::Display for OwnedBadSignature<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(f, false)
+    }
+}
+
+impl<'e, V: fmt::Debug + AsRef<[u8]>> fmt::Display for Unredacted<'e, OwnedBadSignature<V>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.display(f, true)
+    }
+}
+
+impl<V: fmt::Debug + AsRef<[u8]>> error::Error for OwnedBadSignature<V> {
+    fn description(&self) -> &str {
+        match *self {
+            OwnedBadSignature::SeparatorNotFound { .. } => "separator not found",
+            OwnedBadSignature::SignatureMismatch { .. } => "signature does not match",
+            OwnedBadSignature::PayloadInvalid { .. } => "payload invalid",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            OwnedBadSignature::PayloadInvalid { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<V: fmt::Debug + AsRef<[u8]>> OwnedBadTimedSignature<V> {
+    /// See [`BadTimedSignature::unredacted`].
+    pub fn unredacted(&self) -> Unredacted<'_, Self> {
+        Unredacted(self)
+    }
+
+    fn display(&self, f: &mut fmt::Formatter, unredacted: bool) -> fmt::Result {
+        match self {
+            OwnedBadTimedSignature::SeparatorNotFound { separator, .. } => {
+                write!(f, "Separator {:?} not found in value.", separator.0)
+            }
+            OwnedBadTimedSignature::SignatureMismatch { signature, .. } => write!(
+                f,
+                "Signature {} does not match.",
+                Sensitive::new(signature, unredacted)
+            ),
+            OwnedBadTimedSignature::PayloadInvalid { value, error } => {
+                display_payload_invalid(f, value, error, unredacted)
+            }
+            OwnedBadTimedSignature::TimestampMissing { .. } => write!(f, "Timestamp missing"),
+            OwnedBadTimedSignature::TimestampInvalid { timestamp } => write!(
+                f,
+                "Timestamp {} is invalid",
+                Sensitive::new(timestamp, unredacted)
+            ),
+            OwnedBadTimedSignature::TimestampExpired {
+                timestamp, max_age, ..
+            } => write!(
+                f,
+                "Timestamp {:?} is older than {:?} and is expired.",
+                timestamp, max_age
+            ),
+            OwnedBadTimedSignature::TimestampInFuture {
+                timestamp,
+                min_age,
+                max_future_skew,
+                ..
+            } => display_in_future(f, *timestamp, *min_age, *max_future_skew),
+            OwnedBadTimedSignature::TokenExpired { expires_at, .. } => {
+                write!(f, "Token expired at {:?}.", expires_at)
+            }
+            OwnedBadTimedSignature::TokenNotYetValid { not_before, .. } => {
+                write!(f, "Token is not valid before {:?}.", not_before)
+            }
+        }
+    }
+}
+
+impl<V: fmt::Debug + AsRef<[u8]>> fmt::Display for OwnedBadTimedSignature<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display(f, false)
+    }
+}
+
+impl<'e, V: fmt::Debug + AsRef<[u8]>> fmt::Display for Unredacted<'e, OwnedBadTimedSignature<V>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.display(f, true)
+    }
+}
+
+impl<V: fmt::Debug + AsRef<[u8]>> error::Error for OwnedBadTimedSignature<V> {
+    fn description(&self) -> &str {
+        match *self {
+            OwnedBadTimedSignature::SeparatorNotFound { .. } => "separator not found",
+            OwnedBadTimedSignature::SignatureMismatch { .. } => "signature does not match",
+            OwnedBadTimedSignature::TimestampMissing { .. } => "timestamp missing",
+            OwnedBadTimedSignature::TimestampInvalid { .. } => "timestamp invalid",
+            OwnedBadTimedSignature::TimestampExpired { .. } => "timestamp expired",
+            OwnedBadTimedSignature::TimestampInFuture { .. } => "timestamp in future",
+            OwnedBadTimedSignature::TokenExpired { .. } => "token expired",
+            OwnedBadTimedSignature::TokenNotYetValid { .. } => "token not yet valid",
+            OwnedBadTimedSignature::PayloadInvalid { .. } => "payload invalid",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            OwnedBadTimedSignature::PayloadInvalid { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<V> From<OwnedBadSignature<V>> for OwnedBadTimedSignature<V> {
+    fn from(bad_signature: OwnedBadSignature<V>) -> Self {
+        match bad_signature {
+            OwnedBadSignature::SeparatorNotFound { separator } => {
+                OwnedBadTimedSignature::SeparatorNotFound { separator }
+            }
+            OwnedBadSignature::SignatureMismatch { signature, value } => {
+                OwnedBadTimedSignature::SignatureMismatch { signature, value }
+            }
+            OwnedBadSignature::PayloadInvalid { error, value } => {
+                OwnedBadTimedSignature::PayloadInvalid { error, value }
+            }
+        }
+    }
+}
+
+impl<'a, V: ?Sized> From<BadSignature<'a, V>> for BadTimedSignature<'a, V> {
+    fn from(bad_signature: BadSignature<'a, V>) -> Self {
         match bad_signature {
             BadSignature::SeparatorNotFound { separator } => {
                 BadTimedSignature::SeparatorNotFound { separator }
@@ -158,7 +941,7 @@ impl fmt::Display for InvalidSeparator {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "Separator {:?} is in the base64 alphabet, and thus cannot be used",
+            "Separator {:?} is empty or in the alphabet of the encoder, and thus cannot be used",
             self.0
         )
     }
@@ -168,9 +951,27 @@ impl error::Error for InvalidSeparator {
     fn description(&self) -> &str {
         "invalid separator"
     }
+}
 
-    fn cause(&self) -> Option<&dyn error::Error> {
-        None
+impl fmt::Display for UnknownAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnknownAlgorithm::SigningAlgorithm(name) => {
+                write!(f, "Signing algorithm {:?} is not known.", name)
+            }
+            UnknownAlgorithm::KeyDerivation(name) => {
+                write!(f, "Key derivation {:?} is not known.", name)
+            }
+        }
+    }
+}
+
+impl error::Error for UnknownAlgorithm {
+    fn description(&self) -> &str {
+        match *self {
+            UnknownAlgorithm::SigningAlgorithm(_) => "unknown signing algorithm",
+            UnknownAlgorithm::KeyDerivation(_) => "unknown key derivation",
+        }
     }
 }
 
@@ -184,13 +985,9 @@ impl error::Error for SeparatorNotFound {
     fn description(&self) -> &str {
         "separator not foundr"
     }
-
-    fn cause(&self) -> Option<&dyn error::Error> {
-        None
-    }
 }
 
-impl<'a> From<SeparatorNotFound> for BadSignature<'a> {
+impl<'a, V: ?Sized> From<SeparatorNotFound> for BadSignature<'a, V> {
     fn from(error: SeparatorNotFound) -> Self {
         BadSignature::SeparatorNotFound {
             separator: error.separator,
@@ -198,7 +995,7 @@ impl<'a> From<SeparatorNotFound> for BadSignature<'a> {
     }
 }
 
-impl<'a> From<SeparatorNotFound> for BadTimedSignature<'a> {
+impl<'a, V: ?Sized> From<SeparatorNotFound> for BadTimedSignature<'a, V> {
     fn from(error: SeparatorNotFound) -> Self {
         BadTimedSignature::SeparatorNotFound {
             separator: error.separator,
@@ -230,12 +1027,113 @@ impl<T> error::Error for TimestampExpired<T> {
     fn description(&self) -> &str {
         "timestamp expired"
     }
+}
+
+impl PayloadError {
+    fn reason(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "serializer")]
+            PayloadError::Serde(_) => "not valid JSON",
+            PayloadError::Base64(_) => "not valid base64",
+            PayloadError::Utf8Error(_) => "not valid utf-8",
+        }
+    }
+}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Payload is {}.", self.reason())
+    }
+}
+
+impl error::Error for PayloadError {
+    fn description(&self) -> &str {
+        match *self {
+            #[cfg(feature = "serializer")]
+            PayloadError::Serde(_) => "invalid json",
+            PayloadError::Base64(_) => "invalid base64",
+            PayloadError::Utf8Error(_) => "invalid utf-8",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "serializer")]
+            PayloadError::Serde(error) => Some(RedactedJsonError::from_ref(error)),
+            // `base64` only implements `Error` with its `std` feature.
+            #[cfg(feature = "std")]
+            PayloadError::Base64(error) => Some(error),
+            #[cfg(not(feature = "std"))]
+            PayloadError::Base64(_) => None,
+            PayloadError::Utf8Error(error) => Some(error),
+        }
+    }
+}
+
+/// A JSON error, as returned by the [`source`] of a [`PayloadError`], whose [`Display`]
+/// output only shows the category and position of the error. The `Display` output of
+/// [`serde_json::Error`] can quote the payload, so it is only available through
+/// [`get_ref`] and the [`Debug`] output. Requires the `serializer` feature.
+///
+/// # Example
+/// ```rust
+/// use std::error::Error;
+/// use itsdangerous::{default_builder, serializer_with_signer, NullEncoding, RedactedJsonError};
+/// use itsdangerous::{AsSigner, Serializer, Signer};
+///
+/// let serializer = serializer_with_signer(default_builder("secret key").build(), NullEncoding);
+/// let signed = serializer.as_signer().sign("\"secret\"");
+/// let error = serializer.unsign::<u64>(&signed).unwrap_err();
+/// let json_error = error.source().unwrap().source().unwrap();
+/// assert_eq!(json_error.to_string(), "JSON data error at line 1 column 8");
+/// let json_error = json_error.downcast_ref::<RedactedJsonError>().unwrap();
+/// assert!(json_error.get_ref().to_string().contains("secret"));
+/// ```
+///
+/// [`source`]: error::Error::source
+/// [`Display`]: fmt::Display
+/// [`Debug`]: fmt::Debug
+/// [`get_ref`]: RedactedJsonError::get_ref
+#[cfg(feature = "serializer")]
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct RedactedJsonError(serde_json::Error);
 
-    fn cause(&self) -> Option<&dyn error::Error> {
-        None
+#[cfg(feature = "serializer")]
+impl RedactedJsonError {
+    fn from_ref(error: &serde_json::Error) -> &Self {
+        // SAFETY: `RedactedJsonError` is a `repr(transparent)` wrapper of `serde_json::Error`.
+        unsafe { &*(error as *const serde_json::Error as *const Self) }
+    }
+
+    /// Returns the underlying error, whose `Display` output can quote the payload.
+    pub fn get_ref(&self) -> &serde_json::Error {
+        &self.0
     }
 }
 
+#[cfg(feature = "serializer")]
+impl fmt::Display for RedactedJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let category = match self.0.classify() {
+            serde_json::error::Category::Io => "I/O",
+            serde_json::error::Category::Syntax => "syntax",
+            serde_json::error::Category::Data => "data",
+            serde_json::error::Category::Eof => "end of input",
+        };
+        write!(
+            f,
+            "JSON {} error at line {} column {}",
+            category,
+            self.0.line(),
+            self.0.column()
+        )
+    }
+}
+
+#[cfg(feature = "serializer")]
+impl error::Error for RedactedJsonError {}
+
 impl From<base64::DecodeError> for PayloadError {
     fn from(error: base64::DecodeError) -> Self {
         PayloadError::Base64(error)
@@ -255,3 +1153,37 @@ impl From<str::Utf8Error> for PayloadError {
         PayloadError::Utf8Error(error)
     }
 }
+
+impl fmt::Display for InvalidKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidKey::PrivateKey(index) => write!(f, "Private key {} is not valid.", index),
+            InvalidKey::PublicKey(index) => write!(f, "Public key {} is not valid.", index),
+        }
+    }
+}
+
+impl error::Error for InvalidKey {
+    fn description(&self) -> &str {
+        match *self {
+            InvalidKey::PrivateKey(_) => "invalid private key",
+            InvalidKey::PublicKey(_) => "invalid public key",
+        }
+    }
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Buffer of {} bytes is too small, {} bytes are required.",
+            self.available, self.required
+        )
+    }
+}
+
+impl error::Error for BufferTooSmall {
+    fn description(&self) -> &str {
+        "buffer too small"
+    }
+}
@@ -0,0 +1,171 @@
+use crate::error::BadTimedSignature;
+use crate::time::{Duration, SystemTime};
+
+/// Limits on the age of timestamps, which a timed signer enforces when unsigning values, so
+/// that callers do not have to remember to call [`UnsignedValue::value_if_not_expired`].
+///
+/// By default, there is no maximum age, no minimum age, and timestamps in the future are
+/// rejected. Use [`with_max_future_skew`] to accept timestamps from signers whose clock is
+/// slightly ahead of the one that unsigns them.
+///
+/// Values are rejected with [`BadTimedSignature::TimestampExpired`] if their timestamp is
+/// older than the maximum age, and with [`BadTimedSignature::TimestampInFuture`] if it is
+/// younger than the minimum age, allowing for the future skew.
+///
+/// Requires the `std` feature, as the timestamps are checked against the current time of the
+/// signer's [`Clock`].
+///
+/// # Example
+/// ```rust
+/// use itsdangerous::clock::FixedClock;
+/// use itsdangerous::time::{Duration, UNIX_EPOCH};
+/// use itsdangerous::{default_builder, ExpiryPolicy, IntoTimestampSigner, TimestampSigner};
+///
+/// let now = UNIX_EPOCH + Duration::from_secs(1560181622);
+/// let signer = default_builder("secret key")
+///     .with_clock(FixedClock(now))
+///     .build()
+///     .into_timestamp_signer()
+///     .with_expiry_policy(
+///         ExpiryPolicy::new()
+///             .with_max_age(Duration::from_secs(60))
+///             .with_max_future_skew(Duration::from_secs(5)),
+///     );
+///
+/// let signed = signer.sign_with_timestamp("hello", now - Duration::from_secs(30));
+/// assert_eq!(signer.unsign(&signed).unwrap().value(), "hello");
+///
+/// let signed = signer.sign_with_timestamp("hello", now - Duration::from_secs(90));
+/// assert!(signer.unsign(&signed).err().unwrap().is_expired());
+///
+/// let signed = signer.sign_with_timestamp("hello", now + Duration::from_secs(10));
+/// assert!(signer.unsign(&signed).err().unwrap().is_in_future());
+/// ```
+///
+/// [`UnsignedValue::value_if_not_expired`]: crate::UnsignedValue::value_if_not_expired
+/// [`Clock`]: crate::clock::Clock
+/// [`with_max_future_skew`]: ExpiryPolicy::with_max_future_skew
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ExpiryPolicy {
+    max_age: Option<Duration>,
+    max_future_skew: Duration,
+    min_age: Duration,
+}
+
+impl ExpiryPolicy {
+    /// Constructs a new policy, which only rejects timestamps in the future.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects timestamps that are older than `max_age`.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Accepts timestamps that are up to `max_future_skew` in the future, to allow for
+    /// differences between the clocks of the signer and the unsigner.
+    pub fn with_max_future_skew(mut self, max_future_skew: Duration) -> Self {
+        self.max_future_skew = max_future_skew;
+        self
+    }
+
+    /// Rejects timestamps that are younger than `min_age`, e.g. to prevent a value from being
+    /// used right after it was signed.
+    pub fn with_min_age(mut self, min_age: Duration) -> Self {
+        self.min_age = min_age;
+        self
+    }
+
+    /// The maximum age of timestamps, if any.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// How far in the future timestamps may be.
+    pub fn max_future_skew(&self) -> Duration {
+        self.max_future_skew
+    }
+
+    /// The minimum age of timestamps.
+    pub fn min_age(&self) -> Duration {
+        self.min_age
+    }
+
+    /// Checks the `timestamp` of `value` against this policy at the time `now`.
+    pub(crate) fn check<'a, V: ?Sized>(
+        &self,
+        now: SystemTime,
+        timestamp: SystemTime,
+        value: &'a V,
+    ) -> Result<(), BadTimedSignature<'a, V>> {
+        let too_young = match now.duration_since(timestamp) {
+            Ok(age) => match self.max_age {
+                Some(max_age) if age > max_age => {
+                    return Err(BadTimedSignature::TimestampExpired {
+                        timestamp,
+                        max_age,
+                        value,
+                    });
+                }
+                _ => age.saturating_add(self.max_future_skew) < self.min_age,
+            },
+            // The timestamp is in the future, by `error.duration()`.
+            Err(error) => error.duration().saturating_add(self.min_age) > self.max_future_skew,
+        };
+
+        if too_young {
+            Err(BadTimedSignature::TimestampInFuture {
+                timestamp,
+                min_age: self.min_age,
+                max_future_skew: self.max_future_skew,
+                value,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_check() {
+        let now = UNIX_EPOCH + Duration::from_secs(1000);
+        let check = |policy: ExpiryPolicy, offset: i64| {
+            let timestamp = if offset < 0 {
+                now - Duration::from_secs(-offset as u64)
+            } else {
+                now + Duration::from_secs(offset as u64)
+            };
+            policy
+                .check(now, timestamp, "value")
+                .map_err(|error| error.code())
+        };
+
+        let policy = ExpiryPolicy::new();
+        assert_eq!(check(policy, -900), Ok(()));
+        assert_eq!(check(policy, 0), Ok(()));
+        assert_eq!(check(policy, 1), Err("timestamp_in_future"));
+
+        let policy = policy.with_max_age(Duration::from_secs(60));
+        assert_eq!(check(policy, -60), Ok(()));
+        assert_eq!(check(policy, -61), Err("timestamp_expired"));
+
+        let policy = policy.with_max_future_skew(Duration::from_secs(5));
+        assert_eq!(check(policy, 5), Ok(()));
+        assert_eq!(check(policy, 6), Err("timestamp_in_future"));
+
+        let policy = policy.with_min_age(Duration::from_secs(10));
+        assert_eq!(check(policy, -10), Ok(()));
+        // The future skew also applies to the minimum age.
+        assert_eq!(check(policy, -5), Ok(()));
+        assert_eq!(check(policy, -4), Err("timestamp_in_future"));
+        assert_eq!(check(policy, 1), Err("timestamp_in_future"));
+        assert_eq!(check(policy, -61), Err("timestamp_expired"));
+    }
+}
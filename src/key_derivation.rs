@@ -1,42 +1,134 @@
+#[cfg(feature = "hkdf")]
+use alloc::vec::Vec;
+#[cfg(feature = "hkdf")]
+use core::marker::PhantomData;
+#[cfg(feature = "pbkdf2")]
+use core::num::NonZeroU32;
+#[cfg(feature = "hkdf")]
+use core::ops::Mul;
+
 use generic_array::{ArrayLength, GenericArray};
 use hmac::crypto_mac::Mac;
 use hmac::digest::{BlockInput, Digest, FixedOutput, Input, Reset};
+#[cfg(feature = "hkdf")]
+use typenum::{IsLessOrEqual, Prod, True, U255};
 
-/// This trait is called to derive a key for signing from a given key + salt.
+/// This trait is called to derive a key for signing from a given key + salt,
+/// using the given `Digest`.
 ///
 /// ## Remarks
-/// Key derivation is not indended to be used as a security method to make a
-/// complex key out of a short password. Instead, you should use large random
-/// secret keys.
-pub trait DeriveKey {
-    fn derive_key<Digest>(secret_key: &str, salt: &str) -> GenericArray<u8, Digest::OutputSize>
-    where
-        Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
-        Digest::BlockSize: ArrayLength<u8> + Clone,
-        Digest::OutputSize: ArrayLength<u8>;
+/// Apart from `Pbkdf2`, key derivation is not indended to be used as a security
+/// method to make a complex key out of a short password. Instead, you should use
+/// large random secret keys.
+pub trait DeriveKey<Digest> {
+    type OutputSize: ArrayLength<u8>;
+
+    fn derive_key(&self, secret_key: &[u8], salt: &[u8]) -> GenericArray<u8, Self::OutputSize>;
 }
 
 /// Derives a key by doing `digest(salt + secret_key)`
+#[derive(Debug, Default, Copy, Clone)]
 pub struct Concat;
 
 /// Derives a key by doing `digest(salt + "signer" + secret_key)`
+#[derive(Debug, Default, Copy, Clone)]
 pub struct DjangoConcat;
 
 /// Derives a secret key by doing `hmac<digest>(secret_key, input=salt)`
+#[derive(Debug, Default, Copy, Clone)]
 pub struct Hmac;
 
+/// Derives a key of `OutputSize` bytes using HKDF ([RFC 5869]) with `hmac<digest>`, where
+/// the secret key is the input keying material, and the salt is the HKDF salt. Requires
+/// the `hkdf` feature.
+///
+/// Deriving keys with different `info` from the same secret key and salt produces
+/// independent keys, which makes this suitable for deriving several subkeys.
+///
+/// # Example
+/// ```rust
+/// use itsdangerous::key_derivation::Hkdf;
+/// use itsdangerous::{default_builder, Signer};
+///
+/// let signer = default_builder("secret key")
+///     .with_key_derivation(Hkdf::new("session cookies"))
+///     .build();
+/// let signed = signer.sign("hello world!");
+/// # assert_eq!(signer.unsign(&signed).unwrap(), "hello world!");
+/// ```
+///
+/// [RFC 5869]: https://tools.ietf.org/html/rfc5869
+#[cfg(feature = "hkdf")]
+#[derive(Debug, Clone)]
+pub struct Hkdf<OutputSize = DigestOutputSize> {
+    info: Vec<u8>,
+    _phantom: PhantomData<OutputSize>,
+}
+
+/// Derives a key by doing `pbkdf2<hmac<digest>>(secret_key, salt, iterations)`. Requires
+/// the `pbkdf2` feature.
+///
+/// Unlike the other key derivations, this is intended to be used with human-chosen secret
+/// keys. The key is only derived once, when the signer is built, so the number of iterations
+/// does not affect the speed of signing or unsigning.
+///
+/// # Example
+/// ```rust
+/// use std::num::NonZeroU32;
+/// use itsdangerous::key_derivation::Pbkdf2;
+/// use itsdangerous::{default_builder, Signer};
+///
+/// let iterations = NonZeroU32::new(100_000).unwrap();
+/// let signer = default_builder("correct horse battery staple")
+///     .with_key_derivation(Pbkdf2::new(iterations))
+///     .build();
+/// let signed = signer.sign("hello world!");
+/// # assert_eq!(signer.unsign(&signed).unwrap(), "hello world!");
+/// ```
+#[cfg(feature = "pbkdf2")]
+#[derive(Debug, Copy, Clone)]
+pub struct Pbkdf2 {
+    iterations: NonZeroU32,
+}
+
+/// The size of a derived key, which is either a [`typenum`] unsigned integer (e.g. `U32`),
+/// or [`DigestOutputSize`].
+///
+/// [`typenum`]: generic_array::typenum
+pub trait KeySize<Digest> {
+    type OutputSize: ArrayLength<u8>;
+}
+
+/// Makes the derived key the same size as the output of the digest.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct DigestOutputSize;
+
+impl<Digest: FixedOutput> KeySize<Digest> for DigestOutputSize {
+    type OutputSize = Digest::OutputSize;
+}
+
+impl<Digest, U, B> KeySize<Digest> for typenum::UInt<U, B>
+where
+    typenum::UInt<U, B>: ArrayLength<u8>,
+{
+    type OutputSize = Self;
+}
+
 macro_rules! derive_key_impl {
     ($type:ty, ($secret_key:ident, $salt:ident) => $impl: block) => {
-        impl DeriveKey for $type {
-            fn derive_key<Digest>(
-                $secret_key: &str,
-                $salt: &str,
-            ) -> GenericArray<u8, Digest::OutputSize>
-            where
-                Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
-                Digest::BlockSize: ArrayLength<u8> + Clone,
-                Digest::OutputSize: ArrayLength<u8>,
-            {
+        impl<Digest> DeriveKey<Digest> for $type
+        where
+            Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
+            Digest::BlockSize: ArrayLength<u8> + Clone,
+            Digest::OutputSize: ArrayLength<u8>,
+        {
+            type OutputSize = Digest::OutputSize;
+
+            fn derive_key(
+                &self,
+                $secret_key: &[u8],
+                $salt: &[u8],
+            ) -> GenericArray<u8, Digest::OutputSize> {
                 $impl
             }
         }
@@ -45,22 +137,158 @@ macro_rules! derive_key_impl {
 
 derive_key_impl!(Concat, (secret_key, salt) => {
     let mut digest = Digest::new();
-    digest.input(salt.as_bytes());
-    digest.input(secret_key.as_bytes());
+    digest.input(salt);
+    digest.input(secret_key);
     digest.result()
 });
 
 derive_key_impl!(DjangoConcat, (secret_key, salt) => {
     let mut digest = Digest::new();
-    digest.input(salt.as_bytes());
-    digest.input("signer".as_bytes());
-    digest.input(secret_key.as_bytes());
+    digest.input(salt);
+    digest.input(b"signer");
+    digest.input(secret_key);
     digest.result()
 });
 
 derive_key_impl!(Hmac, (secret_key, salt) => {
     let mut mac: hmac::Hmac<Digest> =
-        hmac::Hmac::new_varkey(secret_key.as_bytes()).unwrap();
-    mac.input(salt.as_bytes());
+        hmac::Hmac::new_varkey(secret_key).unwrap();
+    mac.input(salt);
     mac.result().code()
 });
+
+#[cfg(feature = "hkdf")]
+impl Hkdf {
+    /// Constructs a new HKDF key derivation with the given `info`, which binds the derived
+    /// key to a specific purpose. The derived key is the same size as the digest output.
+    pub fn new<I: AsRef<[u8]>>(info: I) -> Self {
+        Self {
+            info: info.as_ref().to_vec(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "hkdf")]
+impl<OutputSize> Hkdf<OutputSize> {
+    /// Derives a key of `N` bytes instead, e.g. `with_output_size::<U32>()`.
+    ///
+    /// HKDF can produce at most 255 times the size of the digest output, which is checked at
+    /// compile time:
+    /// ```rust,compile_fail
+    /// use itsdangerous::key_derivation::Hkdf;
+    /// use itsdangerous::typenum::U8192;
+    /// use itsdangerous::default_builder;
+    ///
+    /// // SHA-1 can produce at most 5100 bytes.
+    /// let signer = default_builder("secret key")
+    ///     .with_key_derivation(Hkdf::new("info").with_output_size::<U8192>())
+    ///     .build();
+    /// ```
+    pub fn with_output_size<N: ArrayLength<u8>>(self) -> Hkdf<N> {
+        Hkdf {
+            info: self.info,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "hkdf")]
+impl<Digest, OutputSize> DeriveKey<Digest> for Hkdf<OutputSize>
+where
+    Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
+    Digest::BlockSize: ArrayLength<u8> + Clone,
+    Digest::OutputSize: ArrayLength<u8> + Mul<U255>,
+    OutputSize: KeySize<Digest>,
+    OutputSize::OutputSize: IsLessOrEqual<Prod<Digest::OutputSize, U255>, Output = True>,
+{
+    type OutputSize = OutputSize::OutputSize;
+
+    fn derive_key(&self, secret_key: &[u8], salt: &[u8]) -> GenericArray<u8, Self::OutputSize> {
+        let mut key = GenericArray::default();
+        hkdf::Hkdf::<Digest>::new(Some(salt), secret_key)
+            .expand(&self.info, &mut key)
+            .expect("the output size is checked by the trait bounds");
+        key
+    }
+}
+
+#[cfg(feature = "pbkdf2")]
+impl Pbkdf2 {
+    /// Constructs a new PBKDF2 key derivation with the given number of iterations.
+    pub fn new(iterations: NonZeroU32) -> Self {
+        Self { iterations }
+    }
+}
+
+#[cfg(feature = "pbkdf2")]
+impl<Digest> DeriveKey<Digest> for Pbkdf2
+where
+    Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone + Sync,
+    Digest::BlockSize: ArrayLength<u8> + Clone,
+    Digest::OutputSize: ArrayLength<u8>,
+{
+    type OutputSize = Digest::OutputSize;
+
+    fn derive_key(&self, secret_key: &[u8], salt: &[u8]) -> GenericArray<u8, Self::OutputSize> {
+        let mut key = GenericArray::default();
+        pbkdf2::pbkdf2::<hmac::Hmac<Digest>>(
+            secret_key,
+            salt,
+            self.iterations.get() as usize,
+            &mut key,
+        );
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_django_concat() {
+        let key = DeriveKey::<sha1::Sha1>::derive_key(&DjangoConcat, b"hello", b"world");
+        let expected = sha1::Sha1::digest(b"worldsignerhello");
+        assert_eq!(key, expected);
+    }
+
+    #[cfg(feature = "hkdf")]
+    #[test]
+    fn test_hkdf() {
+        use generic_array::typenum::U42;
+
+        // RFC 5869, appendix A.4.
+        let secret_key = [0x0b; 11];
+        let salt = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let hkdf = Hkdf::new([0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9])
+            .with_output_size::<U42>();
+        let key = DeriveKey::<sha1::Sha1>::derive_key(&hkdf, &secret_key, &salt);
+        assert_eq!(
+            key.as_slice(),
+            &b"\x08\x5a\x01\xea\x1b\x10\xf3\x69\x33\x06\x8b\x56\xef\xa5\xad\x81\xa4\xf1\
+               \x4b\x82\x2f\x5b\x09\x15\x68\xa9\xcd\xd4\xf1\x55\xfd\xa2\xc2\x2e\x42\x24\
+               \x78\xd3\x05\xf3\xf8\x96"[..]
+        );
+
+        let key = DeriveKey::<sha1::Sha1>::derive_key(&Hkdf::new("a"), b"hello", b"world");
+        assert_eq!(key.len(), 20);
+        let other_key = DeriveKey::<sha1::Sha1>::derive_key(&Hkdf::new("b"), b"hello", b"world");
+        assert_ne!(key, other_key);
+    }
+
+    #[cfg(feature = "pbkdf2")]
+    #[test]
+    fn test_pbkdf2() {
+        // RFC 6070, test 2.
+        let key = DeriveKey::<sha1::Sha1>::derive_key(
+            &Pbkdf2::new(NonZeroU32::new(2).unwrap()),
+            b"password",
+            b"salt",
+        );
+        assert_eq!(
+            key.as_slice(),
+            &b"\xea\x6c\x01\x4d\xc7\x2d\x6f\x8c\xcd\x1e\xd9\x2a\xce\x1d\x41\xf0\xd8\xde\x89\x57"[..]
+        );
+    }
+}
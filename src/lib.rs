@@ -26,23 +26,47 @@
 //! assert_eq!(unsigned, "hello world!");
 //! ```
 //!
+//! ## `no_std`
+//!
+//! Disabling the default `std` feature builds the crate on `core` + `alloc`. Timed signers
+//! then have no system clock, so timestamps are supplied by the caller, see [`time`]. The
+//! methods which take the current time from a [`Clock`], such as [`TimestampSigner::sign`],
+//! and [`ExpiryPolicy`], are only available with the `std` feature.
+//!
+//! [`Clock`]: clock::Clock
+//!
 //! [`itsdangerous`]: https://github.com/pallets/itsdangerous/
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "nightly", feature(test))]
 
+extern crate alloc;
+
 // TODO: One day un-comment this.
 // #![warn(missing_docs)]
 
+mod asymmetric;
 mod base64;
+mod batch;
 mod error;
+#[cfg(feature = "std")]
+mod expiry;
+mod runtime;
+mod secret;
 mod separator;
 mod signer;
+mod stream;
 mod timed;
 mod timestamp;
 mod traits;
 
 pub mod algorithm;
+#[cfg(feature = "std")]
+pub mod clock;
+pub mod dynamic;
+pub mod encoder;
 pub mod key_derivation;
+pub mod time;
 
 #[cfg(feature = "serializer")]
 mod multi_serializer;
@@ -51,20 +75,46 @@ mod serde_serializer;
 #[cfg(feature = "serializer")]
 mod serializer_traits;
 
+#[cfg(feature = "p256")]
+pub use asymmetric::{ecdsa_p256_builder, ecdsa_p256_verifier_builder};
+#[cfg(feature = "ed25519")]
+pub use asymmetric::{ed25519_builder, ed25519_verifier_builder};
+pub use asymmetric::{AsymmetricSignerBuilder, VerifierBuilder, VerifierImpl};
+#[cfg(feature = "serializer")]
+pub use error::RedactedJsonError;
 pub use error::{
-    BadSignature, BadTimedSignature, InvalidSeparator, PayloadError, TimestampExpired,
+    BadSignature, BadTimedSignature, BufferTooSmall, InvalidKey, InvalidSeparator,
+    OwnedBadSignature, OwnedBadTimedSignature, PayloadError, TimestampExpired, UnknownAlgorithm,
+    Unredacted,
 };
+#[cfg(feature = "std")]
+pub use expiry::ExpiryPolicy;
+pub use runtime::RuntimeSignerBuilder;
 pub use separator::Separator;
-pub use signer::{default_builder, SignerBuilder};
-pub use timed::UnsignedValue;
-pub use traits::{AsSigner, IntoTimestampSigner, Signer, TimestampSigner};
+#[cfg(feature = "blake2")]
+pub use signer::blake2b_builder;
+#[cfg(feature = "sha3")]
+pub use signer::sha3_256_builder;
+pub use signer::{
+    default_builder, default_builder_bytes, default_builder_with_secret_keys, SignerBuilder,
+};
+#[cfg(feature = "sha2")]
+pub use signer::{sha256_builder, sha512_builder};
+pub use stream::SignatureStream;
+pub use timed::{ExpiringValue, TimestampSignerImpl, TimestampVerifierImpl, UnsignedValue};
+pub use traits::{
+    AsSigner, IntoTimestampSigner, IntoTimestampVerifier, Signer, StreamingSigner, TimestampSigner,
+    TimestampVerifier, Verifier,
+};
+/// Re-exported, as type-level sizes such as those of truncated signatures are `typenum` numbers.
+pub use typenum;
 
 #[cfg(feature = "serializer")]
 pub use multi_serializer::MultiSerializer;
 #[cfg(feature = "serializer")]
 pub use serde_serializer::{
-    serializer_with_signer, timed_serializer_with_signer, NullEncoding, URLSafeEncoding,
-    UnsignedTimedSerializerValue, UnverifiedTimedValue, UnverifiedValue,
+    serializer_with_signer, timed_serializer_with_signer, Base64Encoding, NullEncoding,
+    URLSafeEncoding, UnsignedTimedSerializerValue, UnverifiedTimedValue, UnverifiedValue,
 };
 #[cfg(feature = "serializer")]
 pub use serializer_traits::{Encoding, Serializer, TimedSerializer};
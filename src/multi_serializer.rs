@@ -1,3 +1,5 @@
+use std::fmt;
+
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::serializer_traits::UnsignToString;
@@ -70,6 +72,18 @@ where
     }
 }
 
+impl<PrimarySerializer> fmt::Debug for MultiSerializer<PrimarySerializer>
+where
+    PrimarySerializer: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MultiSerializer")
+            .field("primary_serializer", &self.primary_serializer)
+            .field("fallback_serializers", &self.fallback_serializers.len())
+            .finish()
+    }
+}
+
 impl<PrimarySerializer> Serializer for MultiSerializer<PrimarySerializer>
 where
     PrimarySerializer: Serializer,
@@ -78,6 +92,10 @@ where
         self.primary_serializer.sign(value)
     }
 
+    fn sign_into<T: Serialize>(&self, value: &T, target: &mut String) -> serde_json::Result<()> {
+        self.primary_serializer.sign_into(value, target)
+    }
+
     fn unsign<'a, T: DeserializeOwned>(&'a self, value: &'a str) -> Result<T, BadSignature<'a>> {
         let primary_serializer_error = match self.primary_serializer.unsign(value) {
             Ok(unsigned) => return Ok(unsigned),
@@ -0,0 +1,503 @@
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+
+use generic_array::ArrayLength;
+use hmac::digest::{BlockInput, FixedOutput, Input, Reset};
+
+use crate::algorithm::{self, HMACAlgorithm, NoneAlgorithm};
+use crate::dynamic::{DynSigner, DynTimestampSigner};
+use crate::error::UnknownAlgorithm;
+use crate::secret::{zeroize_cow, DebugSalt, RedactedKeys};
+use crate::signer::str_to_bytes;
+use crate::{key_derivation, IntoTimestampSigner, Separator, SignerBuilder};
+
+/// A signer builder whose signing algorithm and key derivation are chosen at runtime,
+/// by name, rather than at compile time using type parameters. This is useful if the
+/// algorithm is read from configuration.
+///
+/// The following signing algorithms are supported:
+///  * `"hmac-sha1"` (the default)
+///  * `"hmac-sha224"`, `"hmac-sha256"`, `"hmac-sha384"` and `"hmac-sha512"`, if the `sha2`
+///    feature is enabled.
+///  * `"hmac-sha3-224"`, `"hmac-sha3-256"`, `"hmac-sha3-384"` and `"hmac-sha3-512"`, if the
+///    `sha3` feature is enabled.
+///  * `"hmac-blake2b"` and `"hmac-blake2s"`, if the `blake2` feature is enabled.
+///  * `"none"`, which performs no signing at all.
+///
+/// The following key derivations are supported:
+///  * `"concat"`, see [`key_derivation::Concat`].
+///  * `"django-concat"` (the default), see [`key_derivation::DjangoConcat`].
+///  * `"hmac"`, see [`key_derivation::Hmac`].
+///
+/// # Example
+/// ```rust
+/// use itsdangerous::RuntimeSignerBuilder;
+///
+/// let signer = RuntimeSignerBuilder::new("secret key")
+///     .with_algorithm("hmac-sha1")
+///     .with_key_derivation("hmac")
+///     .build()
+///     .expect("Unknown algorithm");
+///
+/// let signed = signer.sign("hello world!");
+/// assert_eq!(signer.unsign(&signed).unwrap(), "hello world!");
+///
+/// assert!(RuntimeSignerBuilder::new("secret key").with_algorithm("rot13").build().is_err());
+/// ```
+pub struct RuntimeSignerBuilder {
+    secret_keys: Vec<Cow<'static, [u8]>>,
+    salt: Option<Cow<'static, [u8]>>,
+    separator: Separator,
+    algorithm: Cow<'static, str>,
+    key_derivation: Cow<'static, str>,
+}
+
+impl Drop for RuntimeSignerBuilder {
+    fn drop(&mut self) {
+        self.secret_keys.iter_mut().for_each(zeroize_cow);
+    }
+}
+
+impl fmt::Debug for RuntimeSignerBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RuntimeSignerBuilder")
+            .field("algorithm", &self.algorithm)
+            .field("key_derivation", &self.key_derivation)
+            .field("secret_keys", &RedactedKeys(self.secret_keys.len()))
+            .field("salt", &self.salt.as_ref().map(|salt| DebugSalt(salt)))
+            .field("separator", &self.separator)
+            .finish()
+    }
+}
+
+enum AlgorithmName {
+    None,
+    HmacSha1,
+    #[cfg(feature = "sha2")]
+    HmacSha224,
+    #[cfg(feature = "sha2")]
+    HmacSha256,
+    #[cfg(feature = "sha2")]
+    HmacSha384,
+    #[cfg(feature = "sha2")]
+    HmacSha512,
+    #[cfg(feature = "sha3")]
+    HmacSha3_224,
+    #[cfg(feature = "sha3")]
+    HmacSha3_256,
+    #[cfg(feature = "sha3")]
+    HmacSha3_384,
+    #[cfg(feature = "sha3")]
+    HmacSha3_512,
+    #[cfg(feature = "blake2")]
+    HmacBlake2b,
+    #[cfg(feature = "blake2")]
+    HmacBlake2s,
+}
+
+enum KeyDerivationName {
+    Concat,
+    DjangoConcat,
+    Hmac,
+}
+
+impl AlgorithmName {
+    fn parse(name: &str) -> Result<Self, UnknownAlgorithm> {
+        match name {
+            "none" => Ok(AlgorithmName::None),
+            "hmac-sha1" => Ok(AlgorithmName::HmacSha1),
+            #[cfg(feature = "sha2")]
+            "hmac-sha224" => Ok(AlgorithmName::HmacSha224),
+            #[cfg(feature = "sha2")]
+            "hmac-sha256" => Ok(AlgorithmName::HmacSha256),
+            #[cfg(feature = "sha2")]
+            "hmac-sha384" => Ok(AlgorithmName::HmacSha384),
+            #[cfg(feature = "sha2")]
+            "hmac-sha512" => Ok(AlgorithmName::HmacSha512),
+            #[cfg(feature = "sha3")]
+            "hmac-sha3-224" => Ok(AlgorithmName::HmacSha3_224),
+            #[cfg(feature = "sha3")]
+            "hmac-sha3-256" => Ok(AlgorithmName::HmacSha3_256),
+            #[cfg(feature = "sha3")]
+            "hmac-sha3-384" => Ok(AlgorithmName::HmacSha3_384),
+            #[cfg(feature = "sha3")]
+            "hmac-sha3-512" => Ok(AlgorithmName::HmacSha3_512),
+            #[cfg(feature = "blake2")]
+            "hmac-blake2b" => Ok(AlgorithmName::HmacBlake2b),
+            #[cfg(feature = "blake2")]
+            "hmac-blake2s" => Ok(AlgorithmName::HmacBlake2s),
+            _ => Err(UnknownAlgorithm::SigningAlgorithm(name.to_owned())),
+        }
+    }
+}
+
+impl KeyDerivationName {
+    fn parse(name: &str) -> Result<Self, UnknownAlgorithm> {
+        match name {
+            "concat" => Ok(KeyDerivationName::Concat),
+            "django-concat" => Ok(KeyDerivationName::DjangoConcat),
+            "hmac" => Ok(KeyDerivationName::Hmac),
+            _ => Err(UnknownAlgorithm::KeyDerivation(name.to_owned())),
+        }
+    }
+}
+
+/// Builds a signer for the algorithm and key derivation named in a [`RuntimeSignerBuilder`],
+/// binding it to `$signer` and evaluating `$output`. As each combination produces a signer of
+/// a different type, `$output` is expanded once per combination.
+macro_rules! with_signer {
+    ($builder:ident, $signer:ident => $output:expr) => {{
+        let key_derivation = KeyDerivationName::parse(&$builder.key_derivation)?;
+        match AlgorithmName::parse(&$builder.algorithm)? {
+            AlgorithmName::None => {
+                with_signer!(@key_derivation $builder, key_derivation, sha1::Sha1, NoneAlgorithm, $signer => $output)
+            }
+            AlgorithmName::HmacSha1 => {
+                with_signer!(@hmac $builder, key_derivation, sha1::Sha1, $signer => $output)
+            }
+            #[cfg(feature = "sha2")]
+            AlgorithmName::HmacSha224 => {
+                with_signer!(@hmac $builder, key_derivation, sha2::Sha224, $signer => $output)
+            }
+            #[cfg(feature = "sha2")]
+            AlgorithmName::HmacSha256 => {
+                with_signer!(@hmac $builder, key_derivation, sha2::Sha256, $signer => $output)
+            }
+            #[cfg(feature = "sha2")]
+            AlgorithmName::HmacSha384 => {
+                with_signer!(@hmac $builder, key_derivation, sha2::Sha384, $signer => $output)
+            }
+            #[cfg(feature = "sha2")]
+            AlgorithmName::HmacSha512 => {
+                with_signer!(@hmac $builder, key_derivation, sha2::Sha512, $signer => $output)
+            }
+            #[cfg(feature = "sha3")]
+            AlgorithmName::HmacSha3_224 => {
+                with_signer!(@hmac $builder, key_derivation, sha3::Sha3_224, $signer => $output)
+            }
+            #[cfg(feature = "sha3")]
+            AlgorithmName::HmacSha3_256 => {
+                with_signer!(@hmac $builder, key_derivation, sha3::Sha3_256, $signer => $output)
+            }
+            #[cfg(feature = "sha3")]
+            AlgorithmName::HmacSha3_384 => {
+                with_signer!(@hmac $builder, key_derivation, sha3::Sha3_384, $signer => $output)
+            }
+            #[cfg(feature = "sha3")]
+            AlgorithmName::HmacSha3_512 => {
+                with_signer!(@hmac $builder, key_derivation, sha3::Sha3_512, $signer => $output)
+            }
+            #[cfg(feature = "blake2")]
+            AlgorithmName::HmacBlake2b => {
+                with_signer!(@hmac $builder, key_derivation, blake2::Blake2b, $signer => $output)
+            }
+            #[cfg(feature = "blake2")]
+            AlgorithmName::HmacBlake2s => {
+                with_signer!(@hmac $builder, key_derivation, blake2::Blake2s, $signer => $output)
+            }
+        }
+    }};
+    (@hmac $builder:ident, $key_derivation:ident, $digest:ty, $signer:ident => $output:expr) => {
+        with_signer!(@key_derivation $builder, $key_derivation, $digest, HMACAlgorithm<$digest>, $signer => $output)
+    };
+    (@key_derivation $builder:ident, $key_derivation:ident, $digest:ty, $algorithm:ty, $signer:ident => $output:expr) => {
+        match $key_derivation {
+            KeyDerivationName::Concat => {
+                let $signer = $builder
+                    .signer_builder::<$digest, $algorithm, key_derivation::Concat>()
+                    .build();
+                $output
+            }
+            KeyDerivationName::DjangoConcat => {
+                let $signer = $builder
+                    .signer_builder::<$digest, $algorithm, key_derivation::DjangoConcat>()
+                    .build();
+                $output
+            }
+            KeyDerivationName::Hmac => {
+                let $signer = $builder
+                    .signer_builder::<$digest, $algorithm, key_derivation::Hmac>()
+                    .build();
+                $output
+            }
+        }
+    };
+}
+
+impl RuntimeSignerBuilder {
+    /// Constructs a new runtime signer builder with a given secret key, using the
+    /// `"hmac-sha1"` algorithm and `"django-concat"` key derivation by default.
+    pub fn new<S: Into<Cow<'static, str>>>(secret_key: S) -> Self {
+        Self::with_secret_keys(Some(secret_key))
+    }
+
+    /// Constructs a new runtime signer builder with a list of secret keys, ordered from
+    /// oldest to newest. See [`SignerBuilder::with_secret_keys`].
+    ///
+    /// # Panics
+    /// Panics if `secret_keys` is empty.
+    pub fn with_secret_keys<I, S>(secret_keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Cow<'static, str>>,
+    {
+        Self::with_secret_keys_bytes(
+            secret_keys
+                .into_iter()
+                .map(|secret_key| str_to_bytes(secret_key.into())),
+        )
+    }
+
+    /// Constructs a new runtime signer builder with a secret key made of arbitrary bytes.
+    /// See [`SignerBuilder::new_bytes`].
+    pub fn new_bytes<K: Into<Cow<'static, [u8]>>>(secret_key: K) -> Self {
+        Self::with_secret_keys_bytes(Some(secret_key))
+    }
+
+    /// Constructs a new runtime signer builder with a list of secret keys made of arbitrary
+    /// bytes, ordered from oldest to newest. See [`SignerBuilder::with_secret_keys_bytes`].
+    ///
+    /// # Panics
+    /// Panics if `secret_keys` is empty.
+    pub fn with_secret_keys_bytes<I, K>(secret_keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<Cow<'static, [u8]>>,
+    {
+        let secret_keys: Vec<_> = secret_keys.into_iter().map(Into::into).collect();
+        assert!(
+            !secret_keys.is_empty(),
+            "at least one secret key must be provided"
+        );
+
+        Self {
+            secret_keys,
+            salt: None,
+            separator: Default::default(),
+            algorithm: Cow::Borrowed("hmac-sha1"),
+            key_derivation: Cow::Borrowed("django-concat"),
+        }
+    }
+
+    /// Uses a specific salt with the signer. See [`SignerBuilder::with_salt`].
+    pub fn with_salt<S: Into<Cow<'static, str>>>(self, salt: S) -> Self {
+        self.with_salt_bytes(str_to_bytes(salt.into()))
+    }
+
+    /// Uses a specific salt made of arbitrary bytes with the signer.
+    /// See [`SignerBuilder::with_salt_bytes`].
+    pub fn with_salt_bytes<S: Into<Cow<'static, [u8]>>>(mut self, salt: S) -> Self {
+        self.salt = Some(salt.into());
+        self
+    }
+
+    /// Uses a specific separator with the signer. See [`SignerBuilder::with_separator`].
+    pub fn with_separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Uses the signing algorithm with the given name, e.g. `"hmac-sha256"`.
+    pub fn with_algorithm<S: Into<Cow<'static, str>>>(mut self, algorithm: S) -> Self {
+        self.algorithm = algorithm.into();
+        self
+    }
+
+    /// Uses the key derivation with the given name, e.g. `"django-concat"`.
+    pub fn with_key_derivation<S: Into<Cow<'static, str>>>(mut self, key_derivation: S) -> Self {
+        self.key_derivation = key_derivation.into();
+        self
+    }
+
+    /// Builds a boxed [`Signer`] using the configuration specified in this builder.
+    ///
+    /// Returns [`UnknownAlgorithm`] if the signing algorithm or key derivation is not known.
+    ///
+    /// [`Signer`]: crate::Signer
+    pub fn build(self) -> Result<Box<dyn DynSigner + Send + Sync>, UnknownAlgorithm> {
+        Ok(with_signer!(self, signer => Box::new(signer)))
+    }
+
+    /// Builds a boxed [`TimestampSigner`] using the configuration specified in this builder.
+    ///
+    /// Returns [`UnknownAlgorithm`] if the signing algorithm or key derivation is not known.
+    ///
+    /// [`TimestampSigner`]: crate::TimestampSigner
+    pub fn build_timestamp_signer(
+        self,
+    ) -> Result<Box<dyn DynTimestampSigner + Send + Sync>, UnknownAlgorithm> {
+        Ok(with_signer!(self, signer => Box::new(signer.into_timestamp_signer())))
+    }
+
+    fn signer_builder<Digest, Algorithm, KeyDerivation>(
+        &self,
+    ) -> SignerBuilder<Digest, Algorithm, KeyDerivation>
+    where
+        Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
+        Digest::BlockSize: ArrayLength<u8> + Clone,
+        Digest::OutputSize: ArrayLength<u8>,
+        Algorithm: algorithm::SymmetricAlgorithm,
+        Algorithm::OutputSize: ArrayLength<u8>,
+        KeyDerivation: key_derivation::DeriveKey<Digest> + Default,
+    {
+        let builder = SignerBuilder::with_secret_keys_bytes(self.secret_keys.iter().cloned())
+            .with_separator(self.separator.clone());
+        match &self.salt {
+            Some(salt) => builder.with_salt_bytes(salt.clone()),
+            None => builder,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::time::{Duration, UNIX_EPOCH};
+
+    use crate::{default_builder, RuntimeSignerBuilder, Signer, UnknownAlgorithm};
+
+    #[test]
+    fn test_default_algorithm() {
+        let signer = RuntimeSignerBuilder::new("hello").build().unwrap();
+        assert_eq!(
+            signer.sign("this is a test"),
+            default_builder("hello").build().sign("this is a test")
+        );
+    }
+
+    #[test]
+    fn test_key_derivations() {
+        // These are compatibility tests against python.
+        let signer = RuntimeSignerBuilder::new("hello")
+            .with_key_derivation("concat")
+            .build()
+            .unwrap();
+        assert_eq!(
+            signer.sign("this is a test"),
+            "this is a test.xdm7ft8pvOd3pWPnEaCSPq7amSo"
+        );
+
+        let signer = RuntimeSignerBuilder::new("hello")
+            .with_key_derivation("hmac")
+            .build()
+            .unwrap();
+        assert_eq!(
+            signer.sign("this is a test"),
+            "this is a test.iyepmNlqIw3Z1U7AuNpqSG2J5DI"
+        );
+    }
+
+    #[test]
+    fn test_bytes_secret_key() {
+        // This is a compatibility test against python, using bytes secret key & salt.
+        let secret_key: Vec<u8> = (0xe0..=0xff).collect();
+        let signer = RuntimeSignerBuilder::new_bytes(secret_key)
+            .with_salt_bytes(&b"\x00salt\xff"[..])
+            .build()
+            .unwrap();
+        assert_eq!(
+            signer.sign("this is a test"),
+            "this is a test.nj6k3z6J8pbGzt1frasOKirgZFU"
+        );
+    }
+
+    #[test]
+    fn test_none_algorithm() {
+        let signer = RuntimeSignerBuilder::new("hello")
+            .with_algorithm("none")
+            .build()
+            .unwrap();
+        assert_eq!(signer.sign("this is a test"), "this is a test.");
+        assert_eq!(signer.unsign("this is a test.").unwrap(), "this is a test");
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn test_hmac_sha256() {
+        let signer = RuntimeSignerBuilder::new("hello")
+            .with_algorithm("hmac-sha256")
+            .build()
+            .unwrap();
+        // This is a compatibility test against python.
+        assert_eq!(
+            signer.sign("this is a test"),
+            "this is a test.b4NcsM6QyyYT00kAcecRYTAuwb3-8_Sci1jm61VBWIM"
+        );
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn test_hmac_sha3() {
+        // These are compatibility tests against python.
+        let vectors = [
+            (
+                "hmac-sha3-224",
+                "this is a test.t54KHyHFndFrBk4KHYznpOFw0RaqZn6yrTbGiQ",
+            ),
+            (
+                "hmac-sha3-256",
+                "this is a test.zxGGNTEWl8n5PICJIiyzdhH588-4XY5pnOi_1kzgrFk",
+            ),
+            (
+                "hmac-sha3-384",
+                "this is a test.nk3vVEqeuMYobw9ijnyYrnmDDCkJI6OEz-Tf54AcQvx13zuGQei50frxwfcZDi4a",
+            ),
+        ];
+        for (algorithm, expected) in vectors.iter() {
+            let signer = RuntimeSignerBuilder::new("hello")
+                .with_algorithm(*algorithm)
+                .build()
+                .unwrap();
+            assert_eq!(signer.sign("this is a test"), *expected);
+        }
+    }
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn test_hmac_blake2s() {
+        let signer = RuntimeSignerBuilder::new("hello")
+            .with_algorithm("hmac-blake2s")
+            .build()
+            .unwrap();
+        // This is a compatibility test against python.
+        assert_eq!(
+            signer.sign("this is a test"),
+            "this is a test.XT8G5lW6W7uLjFfqp1wAMCzuQuYnoQSm0PuCNn6BdjI"
+        );
+    }
+
+    #[test]
+    fn test_timestamp_signer() {
+        let signer = RuntimeSignerBuilder::new("hello")
+            .build_timestamp_signer()
+            .unwrap();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        assert_eq!(
+            signer.sign_with_timestamp("hello world", timestamp),
+            "hello world.XP57dg.uBK_KvrfABr48ZHk6IrBINjpqp8"
+        );
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        match RuntimeSignerBuilder::new("hello")
+            .with_algorithm("hmac-md4")
+            .build()
+        {
+            Err(UnknownAlgorithm::SigningAlgorithm(name)) => assert_eq!(name, "hmac-md4"),
+            _ => panic!("expected an unknown signing algorithm"),
+        }
+
+        match RuntimeSignerBuilder::new("hello")
+            .with_key_derivation("none")
+            .build_timestamp_signer()
+        {
+            Err(UnknownAlgorithm::KeyDerivation(name)) => assert_eq!(name, "none"),
+            _ => panic!("expected an unknown key derivation"),
+        }
+    }
+}
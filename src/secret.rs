@@ -0,0 +1,129 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use core::mem::{self, ManuallyDrop};
+use core::sync::atomic::{self, Ordering};
+use core::{fmt, ptr};
+
+use generic_array::{ArrayLength, GenericArray};
+use zeroize::Zeroize;
+
+/// Key material that is zeroized when it is dropped.
+///
+/// The key is stored on the heap, so that moving a signer around does not leave copies of
+/// it behind. If the `memory-lock` feature is enabled, the pages that hold the key are also
+/// locked into RAM, which prevents them from being written to swap.
+///
+/// # Remarks
+/// Locking memory is best-effort: if the operating system refuses to lock the pages (for
+/// example, because `RLIMIT_MEMLOCK` has been reached), the key is simply left unlocked.
+/// Locks apply to whole pages, so dropping a key may also unlock other keys that share
+/// a page with it.
+pub(crate) struct SecretKey<N: ArrayLength<u8>> {
+    // Declared first so that the pages are unlocked before the key is deallocated.
+    #[cfg(feature = "memory-lock")]
+    _lock: Option<region::LockGuard>,
+    key: Box<GenericArray<u8, N>>,
+}
+
+impl<N: ArrayLength<u8>> SecretKey<N> {
+    pub(crate) fn new(mut key: GenericArray<u8, N>) -> Self {
+        let secret_key = Box::new(key.clone());
+        key.as_mut_slice().zeroize();
+
+        Self {
+            #[cfg(feature = "memory-lock")]
+            _lock: region::lock(secret_key.as_ptr(), N::to_usize()).ok(),
+            key: secret_key,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        self.key.as_slice()
+    }
+}
+
+impl<N: ArrayLength<u8>> Drop for SecretKey<N> {
+    fn drop(&mut self) {
+        self.key.as_mut_slice().zeroize();
+    }
+}
+
+/// State that was computed from a key, such as the keyed inner and outer digests of HMAC,
+/// which is as sensitive as the key itself.
+///
+/// Like a [`SecretKey`], the state is stored on the heap, the pages that hold it are locked
+/// into RAM if the `memory-lock` feature is enabled, and its memory is zeroized when it is
+/// dropped. The state may be any type, so it is zeroized byte by byte after it is dropped.
+pub(crate) struct SecretState<T> {
+    // Declared first so that the pages are unlocked before the state is deallocated.
+    #[cfg(feature = "memory-lock")]
+    _lock: Option<region::LockGuard>,
+    state: Box<ManuallyDrop<T>>,
+}
+
+impl<T> SecretState<T> {
+    pub(crate) fn new(state: T) -> Self {
+        let state = Box::new(ManuallyDrop::new(state));
+
+        Self {
+            #[cfg(feature = "memory-lock")]
+            _lock: region::lock(&*state as *const ManuallyDrop<T>, mem::size_of::<T>()).ok(),
+            state,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn get(&self) -> &T {
+        &self.state
+    }
+}
+
+impl<T> Drop for SecretState<T> {
+    fn drop(&mut self) {
+        let state: *mut ManuallyDrop<T> = &mut *self.state;
+        // SAFETY: the state is dropped exactly once, here, after which its memory is only
+        // overwritten, and never read, before the box is deallocated.
+        unsafe {
+            ManuallyDrop::drop(&mut *state);
+            let bytes = state as *mut u8;
+            for offset in 0..mem::size_of::<T>() {
+                ptr::write_volatile(bytes.add(offset), 0);
+            }
+        }
+        // Prevent the writes from being reordered after the deallocation.
+        atomic::compiler_fence(Ordering::SeqCst);
+    }
+}
+
+/// Zeroizes a secret key that was provided to a builder, if it is owned. Borrowed keys are
+/// `'static`, so they are not ours to wipe.
+pub(crate) fn zeroize_cow(secret_key: &mut Cow<'static, [u8]>) {
+    if let Cow::Owned(secret_key) = secret_key {
+        secret_key.zeroize();
+    }
+}
+
+/// Formats a list of keys for a `Debug` impl, without revealing them.
+pub(crate) struct RedactedKeys(pub(crate) usize);
+
+impl fmt::Debug for RedactedKeys {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            1 => f.write_str("<1 key redacted>"),
+            count => write!(f, "<{} keys redacted>", count),
+        }
+    }
+}
+
+/// Formats a salt for a `Debug` impl, as a string if it is valid utf-8.
+pub(crate) struct DebugSalt<'a>(pub(crate) &'a [u8]);
+
+impl<'a> fmt::Debug for DebugSalt<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match core::str::from_utf8(self.0) {
+            Ok(salt) => fmt::Debug::fmt(salt, f),
+            Err(_) => fmt::Debug::fmt(self.0, f),
+        }
+    }
+}
@@ -1,9 +1,26 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+
 use crate::base64;
+use crate::encoder::{Base64, SignatureEncoder};
 use crate::error::{InvalidSeparator, SeparatorNotFound};
 
-/// A separator character that can be used in [`crate::SignerBuilder::with_separator`].
+/// Every ASCII character, so that separators of a single ASCII character can borrow from it,
+/// and are cloned without allocating, e.g. into [`SeparatorNotFound`].
+static ASCII: [u8; 128] = {
+    let mut ascii = [0; 128];
+    let mut c = 0;
+    while c < ascii.len() {
+        ascii[c] = c as u8;
+        c += 1;
+    }
+    ascii
+};
+
+/// A separator that can be used in [`crate::SignerBuilder::with_separator`].
 ///
-/// This is used to join the various parts of the signed payload.
+/// This is used to join the various parts of the signed payload. A separator is usually a
+/// single character, but it can be any non-empty utf-8 string.
 ///
 /// # Basic Usage
 /// ```rust
@@ -16,8 +33,8 @@ use crate::error::{InvalidSeparator, SeparatorNotFound};
 ///     .with_separator(separator)
 ///     .build();
 /// ```
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct Separator(pub(crate) char);
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Separator(pub(crate) Cow<'static, str>);
 
 impl Separator {
     /// Creates a new separator, checking to make sure it is valid.
@@ -25,27 +42,159 @@ impl Separator {
     /// A valid separator is a character that is not in the
     /// base-64 url-safe alphabet.
     pub fn new(separator: char) -> Result<Self, InvalidSeparator> {
-        if base64::in_alphabet(separator) {
-            Err(InvalidSeparator(separator))
+        Self::for_encoder::<Base64>(separator)
+    }
+
+    /// Creates a new separator from a string, checking to make sure it is valid.
+    ///
+    /// A valid separator is a non-empty string that contains no characters from the
+    /// base-64 url-safe alphabet.
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::Separator;
+    ///
+    /// assert!(Separator::from_string(":").is_ok());
+    /// assert!(Separator::from_string("\u{a7}\u{a7}").is_ok());
+    /// assert!(Separator::from_string("").is_err());
+    /// assert!(Separator::from_string("::a").is_err());
+    /// ```
+    pub fn from_string<S: Into<Cow<'static, str>>>(separator: S) -> Result<Self, InvalidSeparator> {
+        Self::string_for_encoder::<Base64, S>(separator)
+    }
+
+    /// Creates a new separator for signers that use a specific [`SignatureEncoder`],
+    /// checking to make sure it is not in the alphabet of that encoder, or in the base-64
+    /// alphabet that a [`TimestampSigner`] encodes timestamps with, which is given by its
+    /// [`TimestampProfile`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::encoder::{Base62, StandardBase64};
+    /// use itsdangerous::Separator;
+    ///
+    /// assert!(Separator::for_encoder::<Base62>(':').is_ok());
+    /// assert!(Separator::for_encoder::<Base62>('a').is_err());
+    /// // Timestamps are encoded using url-safe base-64.
+    /// assert!(Separator::for_encoder::<Base62>('-').is_err());
+    /// // Timestamps are encoded using the standard alphabet.
+    /// assert!(Separator::for_encoder::<StandardBase64>('-').is_ok());
+    /// ```
+    ///
+    /// [`TimestampSigner`]: crate::TimestampSigner
+    /// [`TimestampProfile`]: SignatureEncoder::TimestampProfile
+    pub fn for_encoder<Encoder: SignatureEncoder>(
+        separator: char,
+    ) -> Result<Self, InvalidSeparator> {
+        let separator = if separator.is_ascii() {
+            let c = separator as usize;
+            Cow::Borrowed(core::str::from_utf8(&ASCII[c..=c]).expect("ASCII is valid utf-8"))
         } else {
-            Ok(Self(separator))
+            Cow::Owned(separator.to_string())
+        };
+        Self::string_for_encoder::<Encoder, _>(separator)
+    }
+
+    /// Creates a new separator from a string for signers that use a specific
+    /// [`SignatureEncoder`], checking to make sure that it is not empty, and that none of
+    /// its characters are in the alphabet of that encoder or of its timestamps, see
+    /// [`Separator::for_encoder`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::encoder::Hex;
+    /// use itsdangerous::Separator;
+    ///
+    /// assert!(Separator::string_for_encoder::<Hex, _>("::").is_ok());
+    /// assert!(Separator::string_for_encoder::<Hex, _>(":a:").is_err());
+    /// assert!(Separator::string_for_encoder::<Hex, _>("--").is_err());
+    /// ```
+    pub fn string_for_encoder<Encoder: SignatureEncoder, S: Into<Cow<'static, str>>>(
+        separator: S,
+    ) -> Result<Self, InvalidSeparator> {
+        let separator = Self(separator.into());
+        if separator.0.is_empty() || !separator.is_valid_for::<Encoder>() {
+            Err(InvalidSeparator(separator.0.into_owned()))
+        } else {
+            Ok(separator)
         }
     }
 
+    /// Returns whether none of the characters of this separator are in the alphabet of
+    /// `Encoder`, or in the alphabet of the timestamps of signers that use it.
+    pub(crate) fn is_valid_for<Encoder: SignatureEncoder>(&self) -> bool {
+        !self
+            .as_str()
+            .chars()
+            .any(|c| Encoder::in_alphabet(c) || base64::in_alphabet::<Encoder::TimestampProfile>(c))
+    }
+
+    /// Returns the separator as a string.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the separator as utf-8 encoded bytes, which is how it is fed to the signer.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// Splits a string on the last occurrence of this separator.
     #[inline(always)]
     pub fn split<'a>(&self, value: &'a str) -> Result<(&'a str, &'a str), SeparatorNotFound> {
-        let mut iterator = value.rsplitn(2, self.0);
+        let mut iterator = value.rsplitn(2, self.as_str());
         let second = iterator.next().unwrap();
         let first = match iterator.next() {
-            None => return Err(SeparatorNotFound { separator: *self }),
+            None => {
+                return Err(SeparatorNotFound {
+                    separator: self.clone(),
+                })
+            }
             Some(val) => val,
         };
         Ok((first, second))
     }
+
+    /// Splits a byte string on the last occurrence of this separator, the byte
+    /// string equivalent of [`Separator::split`].
+    #[inline(always)]
+    pub fn split_bytes<'a>(
+        &self,
+        value: &'a [u8],
+    ) -> Result<(&'a [u8], &'a [u8]), SeparatorNotFound> {
+        let separator = self.as_bytes();
+        match value
+            .windows(separator.len())
+            .rposition(|window| window == separator)
+        {
+            None => Err(SeparatorNotFound {
+                separator: self.clone(),
+            }),
+            Some(index) => Ok((&value[..index], &value[index + separator.len()..])),
+        }
+    }
 }
 
 impl Default for Separator {
     fn default() -> Self {
-        Self('.')
+        Self(Cow::Borrowed("."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_separator_is_borrowed() {
+        let separator = Separator::new('!').unwrap();
+        assert_eq!(separator.as_str(), "!");
+        assert!(matches!(separator.0, Cow::Borrowed(_)));
+        assert!(matches!(Separator::default().0, Cow::Borrowed(_)));
+
+        let separator = Separator::new('\u{a7}').unwrap();
+        assert_eq!(separator.as_str(), "\u{a7}");
     }
 }
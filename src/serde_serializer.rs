@@ -1,9 +1,10 @@
+use std::marker::PhantomData;
 use std::ops::Deref;
 use std::time::{Duration, SystemTime};
 
 use serde::{de::DeserializeOwned, Serialize};
-use serde_json;
 
+use crate::encoder;
 use crate::error::{BadSignature, BadTimedSignature, PayloadError, TimestampExpired};
 use crate::serializer_traits::UnsignToString;
 use crate::timestamp;
@@ -11,14 +12,50 @@ use crate::{
     base64, AsSigner, Encoding, Separator, Serializer, Signer, TimedSerializer, TimestampSigner,
 };
 
+#[derive(Debug)]
 pub struct NullEncoding;
+#[derive(Debug)]
 pub struct URLSafeEncoding;
 
+/// Encodes payloads as base64 using a given [`Base64Profile`], such as
+/// [`StandardBase64`]. [`URLSafeEncoding`] is equivalent to `Base64Encoding<Base64>`.
+///
+/// # Example
+/// ```rust
+/// use itsdangerous::encoder::StandardBase64;
+/// use itsdangerous::{default_builder, serializer_with_signer, Base64Encoding, Serializer};
+///
+/// let signer = default_builder("secret key").build();
+/// let serializer = serializer_with_signer(signer, Base64Encoding::<StandardBase64>::new());
+/// let signed = serializer.sign(&"hello").unwrap();
+/// assert!(signed.starts_with("ImhlbGxvIg=="));
+/// assert_eq!(serializer.unsign::<String>(&signed).unwrap(), "hello");
+/// ```
+///
+/// [`Base64Profile`]: encoder::Base64Profile
+/// [`StandardBase64`]: encoder::StandardBase64
+#[derive(Debug)]
+pub struct Base64Encoding<Profile>(PhantomData<Profile>);
+
+impl<Profile: encoder::Base64Profile> Base64Encoding<Profile> {
+    pub fn new() -> Self {
+        Base64Encoding(PhantomData)
+    }
+}
+
+impl<Profile: encoder::Base64Profile> Default for Base64Encoding<Profile> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
 pub struct SerializerImpl<TSigner, TEncoding> {
     signer: TSigner,
     encoding: TEncoding,
 }
 
+#[derive(Debug)]
 pub struct TimedSerializerImpl<TSigner, TEncoding> {
     signer: TSigner,
     encoding: TEncoding,
@@ -41,6 +78,10 @@ where
     SerializerImpl { signer, encoding }
 }
 
+/// Constructs a timed serializer. The [`ExpiryPolicy`] of `signer`, if it has one, is enforced
+/// when unsigning values.
+///
+/// [`ExpiryPolicy`]: crate::ExpiryPolicy
 pub fn timed_serializer_with_signer<TSigner, TEncoding>(
     signer: TSigner,
     encoding: TEncoding,
@@ -53,23 +94,34 @@ where
 }
 
 impl Encoding for NullEncoding {
-    fn encode<'a>(&self, serialized_input: String) -> String {
+    fn encode(&self, serialized_input: String) -> String {
         serialized_input
     }
 
-    fn decode<'a>(&self, encoded_input: String) -> Result<String, PayloadError> {
+    fn decode(&self, encoded_input: String) -> Result<String, PayloadError> {
         Ok(encoded_input)
     }
 }
 
 impl Encoding for URLSafeEncoding {
-    fn encode<'a>(&self, serialized_input: String) -> String {
-        base64::encode(&serialized_input)
+    fn encode(&self, serialized_input: String) -> String {
+        base64::encode::<encoder::Base64, _>(&serialized_input)
     }
 
-    fn decode<'a>(&self, encoded_input: String) -> Result<String, PayloadError> {
+    fn decode(&self, encoded_input: String) -> Result<String, PayloadError> {
         // TODO: Handle decompression from... you know... python land.
-        let decoded = base64::decode_str(&encoded_input)?;
+        let decoded = base64::decode_str::<encoder::Base64, _>(&encoded_input)?;
+        Ok(String::from_utf8(decoded).map_err(|e| e.utf8_error())?)
+    }
+}
+
+impl<Profile: encoder::Base64Profile> Encoding for Base64Encoding<Profile> {
+    fn encode(&self, serialized_input: String) -> String {
+        base64::encode::<Profile, _>(&serialized_input)
+    }
+
+    fn decode(&self, encoded_input: String) -> Result<String, PayloadError> {
+        let decoded = base64::decode_str::<Profile, _>(&encoded_input)?;
         Ok(String::from_utf8(decoded).map_err(|e| e.utf8_error())?)
     }
 }
@@ -81,10 +133,7 @@ fn deserialize<'a, T: DeserializeOwned, Encoding: self::Encoding>(
 ) -> Result<T, BadSignature<'a>> {
     let decoded = encoding
         .decode(value.to_string())
-        .map_err(|e| BadSignature::PayloadInvalid {
-            value,
-            error: e.into(),
-        })?;
+        .map_err(|e| BadSignature::PayloadInvalid { value, error: e })?;
     serde_json::from_str(&decoded).map_err(|e| BadSignature::PayloadInvalid {
         value,
         error: e.into(),
@@ -97,9 +146,16 @@ where
     TEncoding: Encoding,
 {
     fn sign<T: Serialize>(&self, value: &T) -> serde_json::Result<String> {
+        let mut signed = String::new();
+        self.sign_into(value, &mut signed)?;
+        Ok(signed)
+    }
+
+    fn sign_into<T: Serialize>(&self, value: &T, target: &mut String) -> serde_json::Result<()> {
         let serialized = serde_json::to_string(value)?;
         let encoded = self.encoding.encode(serialized);
-        Ok(self.signer.sign(encoded))
+        self.signer.sign_into(encoded, target);
+        Ok(())
     }
 
     fn unsign<'a, T: DeserializeOwned>(&'a self, value: &'a str) -> Result<T, BadSignature<'a>> {
@@ -117,10 +173,7 @@ where
         let value = self.signer.unsign(value)?;
         self.encoding
             .decode(value.to_string())
-            .map_err(|e| BadSignature::PayloadInvalid {
-                value,
-                error: e.into(),
-            })
+            .map_err(|e| BadSignature::PayloadInvalid { value, error: e })
     }
 }
 
@@ -141,7 +194,7 @@ where
     TEncoding: Encoding,
 {
     fn sign<T: Serialize>(&self, value: &T) -> serde_json::Result<String> {
-        self.sign_with_timestamp(value, SystemTime::now())
+        self.sign_with_timestamp(value, self.signer.clock().now())
     }
 
     fn sign_with_timestamp<T: Serialize>(
@@ -149,9 +202,26 @@ where
         value: &T,
         timestamp: SystemTime,
     ) -> serde_json::Result<String> {
+        let mut signed = String::new();
+        self.sign_with_timestamp_into(value, timestamp, &mut signed)?;
+        Ok(signed)
+    }
+
+    fn sign_into<T: Serialize>(&self, value: &T, target: &mut String) -> serde_json::Result<()> {
+        self.sign_with_timestamp_into(value, self.signer.clock().now(), target)
+    }
+
+    fn sign_with_timestamp_into<T: Serialize>(
+        &self,
+        value: &T,
+        timestamp: SystemTime,
+        target: &mut String,
+    ) -> serde_json::Result<()> {
         let serialized = serde_json::to_string(value)?;
         let encoded = self.encoding.encode(serialized);
-        Ok(self.signer.sign_with_timestamp(encoded, timestamp))
+        self.signer
+            .sign_with_timestamp_into(encoded, timestamp, target);
+        Ok(())
     }
 
     fn unsign<'a, T: DeserializeOwned>(
@@ -166,6 +236,7 @@ where
         Ok(UnsignedTimedSerializerValue {
             value: deserialized_value,
             timestamp,
+            now: self.signer.clock().now(),
         })
     }
 }
@@ -174,6 +245,8 @@ where
 pub struct UnsignedTimedSerializerValue<T> {
     value: T,
     timestamp: SystemTime,
+    /// The current time of the signer's clock when the value was unsigned.
+    now: SystemTime,
 }
 
 impl<T> UnsignedTimedSerializerValue<T> {
@@ -195,12 +268,22 @@ impl<T> UnsignedTimedSerializerValue<T> {
         self.timestamp
     }
 
-    /// Returns the value if the timestamp is not older than `max_age`.
+    /// Returns the value if the timestamp was not older than `max_age` when the value was
+    /// unsigned, according to the [`Clock`] of the signer. The time is read once when
+    /// unsigning, like [`UnsignedValue::value_if_not_expired`].
     /// In the event that the timestamp is in the future, we'll consider that valid.
     ///
     /// If the value is expired, returns [`TimestampExpired`].
+    ///
+    /// To also reject timestamps in the future, or to have every value checked when it is
+    /// unsigned, give the signer an [`ExpiryPolicy`] instead, see
+    /// [`timed_serializer_with_signer`].
+    ///
+    /// [`Clock`]: crate::clock::Clock
+    /// [`UnsignedValue::value_if_not_expired`]: crate::UnsignedValue::value_if_not_expired
+    /// [`ExpiryPolicy`]: crate::ExpiryPolicy
     pub fn value_if_not_expired(self, max_age: Duration) -> Result<T, TimestampExpired<T>> {
-        match self.timestamp.elapsed() {
+        match self.now.duration_since(self.timestamp) {
             Ok(duration) if duration > max_age => Err(TimestampExpired {
                 timestamp: self.timestamp,
                 value: self.value,
@@ -247,7 +330,7 @@ impl<T> Deref for UnsignedTimedSerializerValue<T> {
 /// // Now, let's say we've gotten that token from somewhere. We need to deserialize it, in order
 /// // to determine the signing key to use. `from_str` will fail if deserialization fails, not if
 /// // the signature is invalid.
-/// let unverified_user_id = UnverifiedValue::<u64>::from_str(Separator::default(), URLSafeEncoding, &token).unwrap();
+/// let unverified_user_id = UnverifiedValue::<u64>::from_str(&Separator::default(), URLSafeEncoding, &token).unwrap();
 /// let serializer = get_serializer(*unverified_user_id.unverified_value());
 /// // We can now attempt to verify the token with a given serializer.
 /// assert_eq!(unverified_user_id.verify(&serializer).unwrap(), 1);
@@ -260,10 +343,10 @@ pub struct UnverifiedValue<'a, T> {
 
 impl<'a, T: DeserializeOwned> UnverifiedValue<'a, T> {
     pub fn from_str<TEncoding: Encoding>(
-        separator: Separator,
+        separator: &Separator,
         encoding: TEncoding,
         input: &'a str,
-    ) -> Result<Self, BadSignature> {
+    ) -> Result<Self, BadSignature<'a>> {
         let (unverified_raw_value, unverified_signature) = separator.split(input)?;
         let unverified_value = deserialize(unverified_raw_value, &encoding)?;
 
@@ -302,15 +385,33 @@ pub struct UnverifiedTimedValue<'a, T> {
 }
 
 impl<'a, T: DeserializeOwned> UnverifiedTimedValue<'a, T> {
+    /// Splits and deserializes a value that was signed by a timed serializer whose signer
+    /// uses the default encoder. Use [`from_str_for_encoder`] if it uses a different one,
+    /// since the encoder determines how timestamps are encoded.
+    ///
+    /// [`from_str_for_encoder`]: UnverifiedTimedValue::from_str_for_encoder
     pub fn from_str<TEncoding: Encoding>(
-        separator: Separator,
+        separator: &Separator,
+        encoding: TEncoding,
+        input: &'a str,
+    ) -> Result<Self, BadTimedSignature<'a>> {
+        Self::from_str_for_encoder::<encoder::Base64, _>(separator, encoding, input)
+    }
+
+    /// Splits and deserializes a value that was signed by a timed serializer whose signer
+    /// uses `Encoder`, decoding the timestamp using its [`TimestampProfile`].
+    ///
+    /// [`TimestampProfile`]: encoder::SignatureEncoder::TimestampProfile
+    pub fn from_str_for_encoder<Encoder: encoder::SignatureEncoder, TEncoding: Encoding>(
+        separator: &Separator,
         encoding: TEncoding,
         input: &'a str,
-    ) -> Result<Self, BadTimedSignature> {
+    ) -> Result<Self, BadTimedSignature<'a>> {
         let (unverified_raw_value, unverified_signature) = separator.split(input)?;
         let (unverified_raw_serialized_value, unverified_timestamp) =
             separator.split(unverified_raw_value)?;
-        let unverified_timestamp = timestamp::decode(unverified_timestamp)?;
+        let unverified_timestamp =
+            timestamp::decode::<Encoder::TimestampProfile, _>(unverified_timestamp)?;
         let unverified_value = deserialize(unverified_raw_serialized_value, &encoding)?;
 
         Ok(UnverifiedTimedValue {
@@ -340,9 +441,15 @@ impl<'a, T: DeserializeOwned> UnverifiedTimedValue<'a, T> {
             .as_signer()
             .verify_encoded_signature(value.as_bytes(), signature.as_bytes())
         {
+            let now = timestamp_signer.clock().now();
+            if let Some(expiry_policy) = timestamp_signer.expiry_policy() {
+                expiry_policy.check(now, self.unverified_timestamp, value)?;
+            }
+
             Ok(UnsignedTimedSerializerValue {
                 value: self.unverified_value,
                 timestamp: self.unverified_timestamp,
+                now,
             })
         } else {
             Err(BadTimedSignature::SignatureMismatch { signature, value })
@@ -356,8 +463,115 @@ mod tests {
 
     use super::*;
     use crate::{default_builder, IntoTimestampSigner};
+
     #[test]
+    fn test_debug_redacts_secret_keys() {
+        let serializer = serializer_with_signer(default_builder("hunter2").build(), NullEncoding);
+        let debug = format!("{:?}", serializer);
+        assert!(!debug.contains("hunter2"), "{}", debug);
+        assert!(debug.contains("encoding: NullEncoding"), "{}", debug);
+
+        let serializer = timed_serializer_with_signer(
+            default_builder("hunter2").build().into_timestamp_signer(),
+            URLSafeEncoding,
+        );
+        let debug = format!("{:?}", serializer);
+        assert!(!debug.contains("hunter2"), "{}", debug);
+        assert!(debug.contains("<1 key redacted>"), "{}", debug);
+    }
 
+    #[test]
+    fn test_unsign_many() {
+        let serializer = serializer_with_signer(default_builder("hello").build(), URLSafeEncoding);
+        let first = serializer.sign(&vec![1, 2, 3]).unwrap();
+        let second = serializer.sign(&vec![4]).unwrap();
+
+        let unsigned: Vec<Result<Vec<u32>, _>> =
+            serializer.unsign_many(vec![first.as_str(), "garbage", second.as_str()]);
+        assert_eq!(unsigned[0].as_ref().unwrap(), &[1, 2, 3]);
+        assert!(unsigned[1].is_err());
+        assert_eq!(unsigned[2].as_ref().unwrap(), &[4]);
+
+        let serializer = timed_serializer_with_signer(
+            default_builder("hello").build().into_timestamp_signer(),
+            URLSafeEncoding,
+        );
+        let signed = serializer.sign(&"hello world").unwrap();
+        let unsigned = serializer.unsign_many::<String, _>(vec![signed.as_str(), "garbage"]);
+        assert_eq!(**unsigned[0].as_ref().unwrap(), "hello world");
+        assert!(unsigned[1].is_err());
+    }
+
+    #[test]
+    fn test_payload_error_source() {
+        use std::error::Error;
+
+        let serializer = serializer_with_signer(default_builder("hello").build(), NullEncoding);
+        let signed = serializer.as_signer().sign("[1,2");
+        let error = serializer.unsign::<Vec<u8>>(&signed).unwrap_err();
+        assert!(error.is_malformed());
+        assert_eq!(error.code(), "payload_invalid");
+
+        let payload_error = error.source().unwrap();
+        assert_eq!(payload_error.to_string(), "Payload is not valid JSON.");
+        let json_error = payload_error.source().unwrap();
+        assert_eq!(
+            json_error.to_string(),
+            "JSON end of input error at line 1 column 4"
+        );
+        assert!(json_error
+            .downcast_ref::<crate::RedactedJsonError>()
+            .unwrap()
+            .get_ref()
+            .is_eof());
+
+        let owned = error.into_owned();
+        let payload_error = owned.source().unwrap();
+        assert!(payload_error
+            .source()
+            .unwrap()
+            .is::<crate::RedactedJsonError>());
+
+        // The errors of serde_json quote the payload, which the sources must not.
+        let signed = serializer.as_signer().sign("\"secret\"");
+        let error = serializer.unsign::<u64>(&signed).unwrap_err();
+        let mut source = error.source();
+        while let Some(error) = source {
+            assert!(!error.to_string().contains("secret"), "{}", error);
+            source = error.source();
+        }
+    }
+
+    #[test]
+    fn test_error_display_is_redacted() {
+        let serializer = serializer_with_signer(default_builder("hello").build(), NullEncoding);
+        let signed = serializer.as_signer().sign(r#"{"password":"hunter2""#);
+        let error = serializer.unsign::<Vec<u8>>(&signed).unwrap_err();
+        let display = error.to_string();
+        assert!(!display.contains("hunter2"), "{}", display);
+        assert!(display.contains("21 bytes"), "{}", display);
+        assert!(
+            display.ends_with("because it is not valid JSON."),
+            "{}",
+            display
+        );
+        assert!(error.unredacted().to_string().contains("hunter2"));
+        assert!(format!("{:?}", error).contains("hunter2"));
+        assert_eq!(error.into_owned().to_string(), display);
+
+        let serializer = timed_serializer_with_signer(
+            default_builder("hello").build().into_timestamp_signer(),
+            URLSafeEncoding,
+        );
+        let signed = serializer.sign(&"hunter2").unwrap();
+        let tampered = format!("{}x", signed);
+        let error = serializer.unsign::<String>(&tampered).err().unwrap();
+        let signature = tampered.rsplit('.').next().unwrap();
+        assert!(!error.to_string().contains(signature));
+        assert!(error.unredacted().to_string().contains(signature));
+    }
+
+    #[test]
     fn test_null_encoding() {
         let s = "hello world".to_owned();
         let encoding = NullEncoding;
@@ -388,7 +602,7 @@ mod tests {
         let signer = default_builder("hello world").build();
         let signed = "[1,2,3].bq_ST5hV4J35lKdovyr_ng-ZIxU";
         let unverified_value: UnverifiedValue<Vec<u8>> =
-            UnverifiedValue::from_str(signer.separator, NullEncoding, signed).unwrap();
+            UnverifiedValue::from_str(&signer.separator, NullEncoding, signed).unwrap();
         let expected = vec![1, 2, 3];
         assert_eq!(unverified_value.unverified_value(), &expected);
         assert_eq!(unverified_value.verify(&signer).unwrap(), expected);
@@ -399,7 +613,7 @@ mod tests {
         let signer = default_builder("not the right key lol").build();
         let signed = "[1,2,3].bq_ST5hV4J35lKdovyr_ng-ZIxU";
         let unverified_value: UnverifiedValue<Vec<u8>> =
-            UnverifiedValue::from_str(signer.separator, NullEncoding, signed).unwrap();
+            UnverifiedValue::from_str(&signer.separator, NullEncoding, signed).unwrap();
         let expected = vec![1, 2, 3];
         assert_eq!(unverified_value.unverified_value(), &expected);
         assert!(unverified_value.verify(&signer).is_err());
@@ -412,6 +626,10 @@ mod tests {
         let signed = "WzEsMiwzXQ.ohh92zNcvFVoWHrPf5uumLp6mbQ";
         assert_eq!(serializer.sign(&vec![1, 2, 3]).unwrap(), signed);
         assert_eq!(serializer.unsign::<Vec<u8>>(signed).unwrap(), vec![1, 2, 3]);
+
+        let mut target = String::from("token=");
+        serializer.sign_into(&vec![1, 2, 3], &mut target).unwrap();
+        assert_eq!(target, format!("token={}", signed));
     }
 
     #[test]
@@ -431,6 +649,12 @@ mod tests {
         let unsigned = serializer.unsign::<Vec<u8>>(signed).unwrap();
         assert_eq!(unsigned.timestamp(), timestamp);
         assert_eq!(unsigned.value(), vec![1, 2, 3]);
+
+        let mut target = String::from("token=");
+        serializer
+            .sign_with_timestamp_into(&vec![1, 2, 3], timestamp, &mut target)
+            .unwrap();
+        assert_eq!(target, format!("token={}", signed));
     }
 
     #[test]
@@ -473,6 +697,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_timed_serializer_with_clock() {
+        use crate::clock::ManualClock;
+
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let clock = ManualClock::new(timestamp);
+        let signer = default_builder("hello world")
+            .with_clock(clock.clone())
+            .build()
+            .into_timestamp_signer();
+        let serializer = timed_serializer_with_signer(signer, NullEncoding);
+
+        let signed = serializer.sign(&vec![1, 2, 3]).unwrap();
+        assert_eq!(signed, "[1,2,3].XP57dg.azFnnbv1s1cilwCeXmeVlMmbqD4");
+
+        clock.advance(Duration::from_secs(30));
+        let unsigned = serializer.unsign::<Vec<u8>>(&signed).unwrap();
+        assert!(unsigned
+            .value_if_not_expired(Duration::from_secs(15))
+            .is_err());
+        let unsigned = serializer.unsign::<Vec<u8>>(&signed).unwrap();
+        assert!(unsigned
+            .value_if_not_expired(Duration::from_secs(30))
+            .is_ok());
+
+        // Like an `UnsignedValue`, the age is checked at the time the value was unsigned.
+        let timestamp_signer = default_builder("hello world")
+            .with_clock(clock.clone())
+            .build()
+            .into_timestamp_signer();
+        let unsigned = serializer.unsign::<Vec<u8>>(&signed).unwrap();
+        let unsigned_value = timestamp_signer.unsign(&signed).unwrap();
+        clock.advance(Duration::from_secs(1));
+        assert!(unsigned
+            .value_if_not_expired(Duration::from_secs(30))
+            .is_ok());
+        assert!(unsigned_value
+            .value_if_not_expired(Duration::from_secs(30))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_timed_serializer_expiry_policy() {
+        use crate::clock::FixedClock;
+        use crate::ExpiryPolicy;
+
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signer = default_builder("hello world")
+            .with_clock(FixedClock(timestamp + Duration::from_secs(30)))
+            .build()
+            .into_timestamp_signer()
+            .with_expiry_policy(ExpiryPolicy::new().with_max_age(Duration::from_secs(15)));
+        let separator = signer.separator().clone();
+        let serializer = timed_serializer_with_signer(signer, NullEncoding);
+        let signed = "[1,2,3].XP57dg.azFnnbv1s1cilwCeXmeVlMmbqD4";
+
+        assert!(serializer
+            .unsign::<Vec<u8>>(signed)
+            .err()
+            .unwrap()
+            .is_expired());
+
+        let unverified_value: UnverifiedTimedValue<Vec<u8>> =
+            UnverifiedTimedValue::from_str(&separator, NullEncoding, signed).unwrap();
+        assert!(unverified_value
+            .verify(&serializer.signer)
+            .err()
+            .unwrap()
+            .is_expired());
+    }
+
+    #[test]
+    fn test_base64_encoding() {
+        use crate::encoder::{StandardBase64, UrlSafePaddedBase64};
+
+        let serializer = serializer_with_signer(
+            default_builder("hello").build(),
+            Base64Encoding::<StandardBase64>::new(),
+        );
+        let signed = serializer.sign(&"\u{fbff}").unwrap();
+        assert!(signed.starts_with("Iu+vvyI=."), "{}", signed);
+        assert_eq!(serializer.unsign::<String>(&signed).unwrap(), "\u{fbff}");
+
+        let encoding = Base64Encoding::<UrlSafePaddedBase64>::new();
+        assert_eq!(encoding.encode("\"\u{fbff}\"".to_owned()), "Iu-vvyI=");
+        assert!(encoding.decode("Iu-vvyI".to_owned()).is_err());
+        assert!(URLSafeEncoding.decode("Iu-vvyI=".to_owned()).is_ok());
+    }
+
     #[test]
     fn test_timed_signer_impl_can_be_used_to_verify() {
         let signer = default_builder("hello world")
@@ -482,13 +795,43 @@ mod tests {
         let serializer = timed_serializer_with_signer(signer, URLSafeEncoding);
         let signed = serializer.sign(&"whatever").unwrap();
         let unverified: UnverifiedTimedValue<String> =
-            UnverifiedTimedValue::from_str(separator, URLSafeEncoding, &signed).unwrap();
+            UnverifiedTimedValue::from_str(&separator, URLSafeEncoding, &signed).unwrap();
         assert_eq!(unverified.unverified_value(), "whatever");
         let verified = unverified
             .verify(serializer.signer())
             .expect("Failed to verify");
         assert_eq!(&verified.value(), "whatever");
     }
+
+    #[test]
+    fn test_unverified_timed_value_for_encoder() {
+        use crate::encoder::StandardBase64;
+
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560214527);
+        let signer = default_builder("hello world")
+            .with_encoder(StandardBase64)
+            .build()
+            .into_timestamp_signer();
+        let separator = signer.separator().clone();
+        let serializer = timed_serializer_with_signer(signer, URLSafeEncoding);
+        let signed = serializer
+            .sign_with_timestamp(&"whatever", timestamp)
+            .unwrap();
+
+        // The timestamp is encoded as `XP77/w==`, which is not url-safe base64.
+        assert!(
+            UnverifiedTimedValue::<String>::from_str(&separator, URLSafeEncoding, &signed).is_err()
+        );
+        let unverified = UnverifiedTimedValue::<String>::from_str_for_encoder::<StandardBase64, _>(
+            &separator,
+            URLSafeEncoding,
+            &signed,
+        )
+        .unwrap();
+        assert_eq!(unverified.unverified_timestamp(), timestamp);
+        let verified = unverified.verify(serializer.signer()).unwrap();
+        assert_eq!(&verified.value(), "whatever");
+    }
 }
 
 #[cfg(all(test, feature = "nightly"))]
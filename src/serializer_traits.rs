@@ -3,16 +3,52 @@ use std::time::SystemTime;
 
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::batch;
 use crate::{BadSignature, BadTimedSignature, PayloadError, UnsignedTimedSerializerValue};
 
 pub trait Encoding {
-    fn encode<'a>(&self, serialized_input: String) -> String;
-    fn decode<'a>(&self, encoded_input: String) -> Result<String, PayloadError>;
+    fn encode(&self, serialized_input: String) -> String;
+    fn decode(&self, encoded_input: String) -> Result<String, PayloadError>;
 }
 
 pub trait Serializer {
     fn sign<T: Serialize>(&self, value: &T) -> serde_json::Result<String>;
+
+    /// Signs the given value, appending the signed value to `target` rather than allocating
+    /// a new [`String`] for it. See [`Signer::sign_into`](crate::Signer::sign_into).
+    ///
+    /// The value is still serialized, and encoded, into temporary buffers before it is
+    /// signed, since the signature covers the encoded payload.
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::{default_builder, serializer_with_signer, Serializer, URLSafeEncoding};
+    ///
+    /// let serializer = serializer_with_signer(default_builder("secret key").build(), URLSafeEncoding);
+    /// let mut signed = String::with_capacity(64);
+    /// for value in &[1, 2] {
+    ///     signed.clear();
+    ///     serializer.sign_into(value, &mut signed).unwrap();
+    ///     assert_eq!(serializer.unsign::<i32>(&signed).unwrap(), *value);
+    /// }
+    /// ```
+    fn sign_into<T: Serialize>(&self, value: &T, target: &mut String) -> serde_json::Result<()> {
+        target.push_str(&self.sign(value)?);
+        Ok(())
+    }
+
     fn unsign<'a, T: DeserializeOwned>(&'a self, value: &'a str) -> Result<T, BadSignature<'a>>;
+
+    /// Unsigns each of the given strings, returning the results in the same order. See
+    /// [`Signer::unsign_many`](crate::Signer::unsign_many).
+    fn unsign_many<'a, T, I>(&'a self, values: I) -> Vec<Result<T, BadSignature<'a>>>
+    where
+        Self: Sync,
+        T: DeserializeOwned + Send,
+        I: IntoIterator<Item = &'a str>,
+    {
+        batch::map_in_order(values, |value| self.unsign(value))
+    }
 }
 
 pub trait TimedSerializer {
@@ -22,10 +58,43 @@ pub trait TimedSerializer {
         value: &T,
         timestamp: SystemTime,
     ) -> serde_json::Result<String>;
+
+    /// Signs the given value with the current time, appending the signed value to `target`.
+    /// See [`Serializer::sign_into`].
+    fn sign_into<T: Serialize>(&self, value: &T, target: &mut String) -> serde_json::Result<()> {
+        target.push_str(&self.sign(value)?);
+        Ok(())
+    }
+
+    /// Signs the given value with an arbitrary timestamp, appending the signed value to
+    /// `target`. See [`Serializer::sign_into`].
+    fn sign_with_timestamp_into<T: Serialize>(
+        &self,
+        value: &T,
+        timestamp: SystemTime,
+        target: &mut String,
+    ) -> serde_json::Result<()> {
+        target.push_str(&self.sign_with_timestamp(value, timestamp)?);
+        Ok(())
+    }
     fn unsign<'a, T: DeserializeOwned>(
         &'a self,
         value: &'a str,
     ) -> Result<UnsignedTimedSerializerValue<T>, BadTimedSignature<'a>>;
+
+    /// Unsigns each of the given strings, returning the results in the same order. See
+    /// [`Signer::unsign_many`](crate::Signer::unsign_many).
+    fn unsign_many<'a, T, I>(
+        &'a self,
+        values: I,
+    ) -> Vec<Result<UnsignedTimedSerializerValue<T>, BadTimedSignature<'a>>>
+    where
+        Self: Sync,
+        T: DeserializeOwned + Send,
+        I: IntoIterator<Item = &'a str>,
+    {
+        batch::map_in_order(values, |value| self.unsign(value))
+    }
 }
 
 pub trait UnsignToString {
@@ -1,24 +1,47 @@
-use std::borrow::Cow;
-use std::marker::PhantomData;
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::{any, fmt, mem};
 
-use generic_array::{ArrayLength, GenericArray};
+use generic_array::ArrayLength;
 use hmac::digest::{BlockInput, FixedOutput, Input, Reset};
-use typenum::Unsigned;
+use typenum::{IsGreaterOrEqual, IsLessOrEqual, True, Unsigned};
 
 use crate::algorithm::{self, Signature, Signer as AlgorithmSigner};
-use crate::base64::{self, Base64Sized, Base64SizedEncoder, URLSafeBase64Encode};
+#[cfg(feature = "std")]
+use crate::clock::{self, Clock};
+use crate::encoder;
 use crate::key_derivation;
+use crate::secret::{zeroize_cow, DebugSalt, RedactedKeys, SecretKey, SecretState};
 use crate::timed::TimestampSignerImpl;
-use crate::traits::GetSigner;
-use crate::{AsSigner, BadSignature, IntoTimestampSigner, Separator, Signer};
+use crate::traits::{GetEncoder, GetSigner};
+use crate::{
+    AsSigner, BadSignature, BufferTooSmall, IntoTimestampSigner, Separator, SignatureStream,
+    Signer, StreamingSigner,
+};
 
-static DEFAULT_SALT: Cow<'static, str> = Cow::Borrowed("itsdangerous.Signer");
+static DEFAULT_SALT: Cow<'static, [u8]> = Cow::Borrowed(b"itsdangerous.Signer");
 
-pub struct SignerBuilder<Digest, Algorithm, KeyDerivation> {
-    secret_key: Cow<'static, str>,
-    salt: Cow<'static, str>,
+/// Converts a string secret key or salt to bytes, without copying it.
+pub(crate) fn str_to_bytes(value: Cow<'static, str>) -> Cow<'static, [u8]> {
+    match value {
+        Cow::Borrowed(value) => Cow::Borrowed(value.as_bytes()),
+        Cow::Owned(value) => Cow::Owned(value.into_bytes()),
+    }
+}
+
+pub struct SignerBuilder<Digest, Algorithm, KeyDerivation, Encoder = encoder::Base64> {
+    /// Secret keys, ordered from oldest to newest. The newest key is used for signing.
+    secret_keys: Vec<Cow<'static, [u8]>>,
+    salt: Cow<'static, [u8]>,
     separator: Separator,
-    _phantom: PhantomData<(Digest, Algorithm, KeyDerivation)>,
+    key_derivation: KeyDerivation,
+    #[cfg(feature = "std")]
+    clock: Option<Box<dyn Clock>>,
+    _phantom: PhantomData<(Digest, Algorithm, Encoder)>,
 }
 
 /// Constructs a default signer builder, using the [`sha1`] digest, [`hmac`],
@@ -31,28 +54,205 @@ pub fn default_builder<S: Into<Cow<'static, str>>>(
     SignerBuilder::new(secret_key)
 }
 
+/// Constructs a default signer builder, like [`default_builder`], but with a secret key
+/// made of arbitrary bytes rather than a string.
+///
+/// This is compatible with the python library when given a `bytes` secret key.
+///
+/// # Example
+/// ```rust
+/// use itsdangerous::{default_builder_bytes, Signer};
+///
+/// let secret_key: Vec<u8> = vec![0xde, 0xad, 0xbe, 0xef];
+/// let signer = default_builder_bytes(secret_key).build();
+/// let signed = signer.sign("hello world!");
+/// # assert_eq!(signer.unsign(&signed).unwrap(), "hello world!");
+/// ```
+pub fn default_builder_bytes<K: Into<Cow<'static, [u8]>>>(
+    secret_key: K,
+) -> SignerBuilder<sha1::Sha1, algorithm::HMACAlgorithm<sha1::Sha1>, key_derivation::DjangoConcat> {
+    SignerBuilder::new_bytes(secret_key)
+}
+
+/// Constructs a default signer builder, like [`default_builder`], but with a list of
+/// secret keys to support key rotation.
+///
+/// The keys are ordered from oldest to newest, matching the python library. The newest
+/// key is used to sign values, and every key will be attempted when unsigning.
+///
+/// # Example
+/// ```rust
+/// use itsdangerous::{default_builder, default_builder_with_secret_keys, Signer};
+///
+/// let signed_with_old_key = default_builder("old key").build().sign("hello");
+///
+/// let signer = default_builder_with_secret_keys(vec!["old key", "new key"]).build();
+/// assert_eq!(signer.unsign(&signed_with_old_key).unwrap(), "hello");
+/// assert_eq!(signer.sign("hello"), default_builder("new key").build().sign("hello"));
+/// ```
+///
+/// # Panics
+/// Panics if `secret_keys` is empty.
+pub fn default_builder_with_secret_keys<I, S>(
+    secret_keys: I,
+) -> SignerBuilder<sha1::Sha1, algorithm::HMACAlgorithm<sha1::Sha1>, key_derivation::DjangoConcat>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<Cow<'static, str>>,
+{
+    SignerBuilder::with_secret_keys(secret_keys)
+}
+
+/// Constructs a signer builder using the [`sha2::Sha256`] digest, [`hmac`], and the
+/// [`django concat`] key derivation. Requires the `sha2` feature.
+///
+/// This is compatible with the python library using `digest_method=hashlib.sha256`.
+///
+/// [`django concat`]: crate::key_derivation::DjangoConcat
+#[cfg(feature = "sha2")]
+pub fn sha256_builder<S: Into<Cow<'static, str>>>(
+    secret_key: S,
+) -> SignerBuilder<sha2::Sha256, algorithm::HMACAlgorithm<sha2::Sha256>, key_derivation::DjangoConcat>
+{
+    SignerBuilder::new(secret_key)
+}
+
+/// Constructs a signer builder using the [`sha2::Sha512`] digest, [`hmac`], and the
+/// [`django concat`] key derivation. Requires the `sha2` feature.
+///
+/// This is compatible with the python library using `digest_method=hashlib.sha512`.
+///
+/// [`django concat`]: crate::key_derivation::DjangoConcat
+#[cfg(feature = "sha2")]
+pub fn sha512_builder<S: Into<Cow<'static, str>>>(
+    secret_key: S,
+) -> SignerBuilder<sha2::Sha512, algorithm::HMACAlgorithm<sha2::Sha512>, key_derivation::DjangoConcat>
+{
+    SignerBuilder::new(secret_key)
+}
+
+/// Constructs a signer builder using the [`sha3::Sha3_256`] digest, [`hmac`], and the
+/// [`django concat`] key derivation. Requires the `sha3` feature.
+///
+/// This is compatible with the python library using `digest_method=hashlib.sha3_256`.
+///
+/// [`django concat`]: crate::key_derivation::DjangoConcat
+#[cfg(feature = "sha3")]
+pub fn sha3_256_builder<S: Into<Cow<'static, str>>>(
+    secret_key: S,
+) -> SignerBuilder<
+    sha3::Sha3_256,
+    algorithm::HMACAlgorithm<sha3::Sha3_256>,
+    key_derivation::DjangoConcat,
+> {
+    SignerBuilder::new(secret_key)
+}
+
+/// Constructs a signer builder using the [`blake2::Blake2b`] digest, [`hmac`], and the
+/// [`django concat`] key derivation. Requires the `blake2` feature.
+///
+/// This is compatible with the python library using `digest_method=hashlib.blake2b`.
+///
+/// [`django concat`]: crate::key_derivation::DjangoConcat
+#[cfg(feature = "blake2")]
+pub fn blake2b_builder<S: Into<Cow<'static, str>>>(
+    secret_key: S,
+) -> SignerBuilder<
+    blake2::Blake2b,
+    algorithm::HMACAlgorithm<blake2::Blake2b>,
+    key_derivation::DjangoConcat,
+> {
+    SignerBuilder::new(secret_key)
+}
+
 impl<Digest, Algorithm, KeyDerivation> SignerBuilder<Digest, Algorithm, KeyDerivation>
 where
     Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
     Digest::BlockSize: ArrayLength<u8> + Clone,
     Digest::OutputSize: ArrayLength<u8>,
-    Algorithm: algorithm::SigningAlgorithm,
+    Algorithm: algorithm::SymmetricAlgorithm,
     Algorithm::OutputSize: ArrayLength<u8>,
-    KeyDerivation: key_derivation::DeriveKey,
+    KeyDerivation: key_derivation::DeriveKey<Digest> + Default,
 {
     /// Constructs a new signer builder with a given secret key.
     pub fn new<S: Into<Cow<'static, str>>>(secret_key: S) -> Self {
+        Self::with_secret_keys(Some(secret_key))
+    }
+
+    /// Constructs a new signer builder with a list of secret keys, ordered from
+    /// oldest to newest.
+    ///
+    /// The newest key is used to sign values. When unsigning, a signature created
+    /// by any of the keys is accepted, with the newest key being attempted first.
+    ///
+    /// # Panics
+    /// Panics if `secret_keys` is empty.
+    pub fn with_secret_keys<I, S>(secret_keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Cow<'static, str>>,
+    {
+        Self::with_secret_keys_bytes(
+            secret_keys
+                .into_iter()
+                .map(|secret_key| str_to_bytes(secret_key.into())),
+        )
+    }
+
+    /// Constructs a new signer builder with a secret key made of arbitrary bytes.
+    pub fn new_bytes<K: Into<Cow<'static, [u8]>>>(secret_key: K) -> Self {
+        Self::with_secret_keys_bytes(Some(secret_key))
+    }
+
+    /// Constructs a new signer builder with a list of secret keys made of arbitrary bytes,
+    /// ordered from oldest to newest. See [`with_secret_keys`].
+    ///
+    /// # Panics
+    /// Panics if `secret_keys` is empty.
+    ///
+    /// [`with_secret_keys`]: SignerBuilder::with_secret_keys
+    pub fn with_secret_keys_bytes<I, K>(secret_keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<Cow<'static, [u8]>>,
+    {
+        let secret_keys: Vec<_> = secret_keys.into_iter().map(Into::into).collect();
+        assert!(
+            !secret_keys.is_empty(),
+            "at least one secret key must be provided"
+        );
+
         Self {
-            secret_key: secret_key.into(),
+            secret_keys,
             salt: DEFAULT_SALT.clone(),
             separator: Default::default(),
+            key_derivation: Default::default(),
+            #[cfg(feature = "std")]
+            clock: None,
             _phantom: PhantomData,
         }
     }
+}
 
+impl<Digest, Algorithm, KeyDerivation, Encoder>
+    SignerBuilder<Digest, Algorithm, KeyDerivation, Encoder>
+where
+    Digest: Input + BlockInput + FixedOutput + Reset + Default + Clone,
+    Digest::BlockSize: ArrayLength<u8> + Clone,
+    Digest::OutputSize: ArrayLength<u8>,
+    Algorithm: algorithm::SymmetricAlgorithm,
+    Algorithm::OutputSize: ArrayLength<u8>,
+    KeyDerivation: key_derivation::DeriveKey<Digest>,
+    Encoder: encoder::SignatureEncoder,
+{
     /// Uses a specific salt with the signer. If no salt is defined, will
     /// default to `DEFAULT_SALT`.
-    pub fn with_salt<S: Into<Cow<'static, str>>>(mut self, salt: S) -> Self {
+    pub fn with_salt<S: Into<Cow<'static, str>>>(self, salt: S) -> Self {
+        self.with_salt_bytes(str_to_bytes(salt.into()))
+    }
+
+    /// Uses a specific salt made of arbitrary bytes with the signer.
+    pub fn with_salt_bytes<S: Into<Cow<'static, [u8]>>>(mut self, salt: S) -> Self {
         self.salt = salt.into();
         self
     }
@@ -64,61 +264,268 @@ where
         self
     }
 
+    /// Uses a specific clock with the timestamp signer that the signer is converted into, see
+    /// [`IntoTimestampSigner`]. If no clock is defined, will default to the system clock.
+    /// Requires the `std` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::clock::FixedClock;
+    /// use itsdangerous::time::{Duration, UNIX_EPOCH};
+    /// use itsdangerous::{default_builder, IntoTimestampSigner, TimestampSigner};
+    ///
+    /// let signer = default_builder("hello")
+    ///     .with_clock(FixedClock(UNIX_EPOCH + Duration::from_secs(1560181622)))
+    ///     .build()
+    ///     .into_timestamp_signer();
+    /// assert_eq!(
+    ///     signer.sign("hello world"),
+    ///     "hello world.XP57dg.uBK_KvrfABr48ZHk6IrBINjpqp8"
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Uses a specific key derivation with the signer, such as `key_derivation::Hkdf` or
+    /// `key_derivation::Pbkdf2`, which can be configured. If no key derivation is defined,
+    /// will default to the one chosen when constructing the builder.
+    pub fn with_key_derivation<K>(
+        mut self,
+        key_derivation: K,
+    ) -> SignerBuilder<Digest, Algorithm, K, Encoder>
+    where
+        K: key_derivation::DeriveKey<Digest>,
+    {
+        SignerBuilder {
+            secret_keys: mem::take(&mut self.secret_keys),
+            salt: mem::take(&mut self.salt),
+            separator: mem::take(&mut self.separator),
+            key_derivation,
+            #[cfg(feature = "std")]
+            clock: self.clock.take(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Uses a specific encoder for signatures, such as `encoder::Hex`. If no encoder is
+    /// defined, will default to url-safe base64, which is compatible with the python library.
+    ///
+    /// A [`Base64Profile`] such as `encoder::StandardBase64` can also be used, which changes
+    /// the base64 alphabet and padding of both signatures and timestamps.
+    ///
+    /// The separator must not be in the alphabet of the encoder, or of the timestamps of
+    /// timed signers that use it, see [`Separator::for_encoder`].
+    ///
+    /// [`Base64Profile`]: encoder::Base64Profile
+    pub fn with_encoder<E>(
+        mut self,
+        _encoder: E,
+    ) -> SignerBuilder<Digest, Algorithm, KeyDerivation, E>
+    where
+        E: encoder::SignatureEncoder,
+        KeyDerivation: Clone,
+    {
+        SignerBuilder {
+            secret_keys: mem::take(&mut self.secret_keys),
+            salt: mem::take(&mut self.salt),
+            separator: mem::take(&mut self.separator),
+            key_derivation: self.key_derivation.clone(),
+            #[cfg(feature = "std")]
+            clock: self.clock.take(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Truncates signatures to their first `N` bytes, which makes signed values shorter at
+    /// the cost of security. `N` must be at least [`MinTruncatedSize`] (10 bytes), which is
+    /// checked at compile time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::typenum::U10;
+    /// use itsdangerous::{default_builder, Signer};
+    ///
+    /// let signer = default_builder("secret key")
+    ///     .with_truncated_signature::<U10>()
+    ///     .build();
+    /// let signed = signer.sign("12345");
+    /// assert_eq!(signed.len(), "12345".len() + 1 + 14);
+    /// assert_eq!(signer.unsign(&signed).unwrap(), "12345");
+    /// ```
+    ///
+    /// [`MinTruncatedSize`]: algorithm::MinTruncatedSize
+    pub fn with_truncated_signature<N>(
+        mut self,
+    ) -> SignerBuilder<Digest, algorithm::TruncatedAlgorithm<Algorithm, N>, KeyDerivation, Encoder>
+    where
+        N: ArrayLength<u8>
+            + Unsigned
+            + IsGreaterOrEqual<algorithm::MinTruncatedSize, Output = True>
+            + IsLessOrEqual<Algorithm::OutputSize, Output = True>,
+        KeyDerivation: Clone,
+    {
+        SignerBuilder {
+            secret_keys: mem::take(&mut self.secret_keys),
+            salt: mem::take(&mut self.salt),
+            separator: mem::take(&mut self.separator),
+            key_derivation: self.key_derivation.clone(),
+            #[cfg(feature = "std")]
+            clock: self.clock.take(),
+            _phantom: PhantomData,
+        }
+    }
+
     /// Builds a Signer using the configuration specified in this builder.
-    pub fn build(
-        self,
-    ) -> SignerImpl<Algorithm, Digest::OutputSize, Base64SizedEncoder<Algorithm::OutputSize>> {
-        let derived_key = KeyDerivation::derive_key::<Digest>(&self.secret_key, &self.salt);
+    ///
+    /// # Panics
+    /// Panics if the separator is in the alphabet of the encoder, or of its timestamps.
+    pub fn build(mut self) -> SignerImpl<Algorithm, KeyDerivation::OutputSize, Encoder> {
+        assert!(
+            self.separator.is_valid_for::<Encoder>(),
+            "the separator must not be in the alphabet of the encoder or of its timestamps"
+        );
 
-        SignerImpl {
+        let salt = &self.salt;
+        let key_derivation = &self.key_derivation;
+        let mut derived_keys = self
+            .secret_keys
+            .iter()
+            .rev()
+            .map(|secret_key| SecretKey::new(key_derivation.derive_key(secret_key, salt)));
+        // The constructors guarantee that there is at least one secret key.
+        let derived_key = derived_keys.next().unwrap();
+        let fallback_derived_keys = derived_keys.collect();
+
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+        let mut signer = SignerImpl::with_derived_keys(
             derived_key,
-            separator: self.separator,
-            _phantom: PhantomData,
+            fallback_derived_keys,
+            Some(salt.clone()),
+            mem::take(&mut self.separator),
+        );
+        #[cfg(feature = "std")]
+        {
+            signer.clock = self.clock.take();
         }
+        signer
+    }
+}
+
+impl<Digest, Algorithm, KeyDerivation, Encoder> Drop
+    for SignerBuilder<Digest, Algorithm, KeyDerivation, Encoder>
+{
+    fn drop(&mut self) {
+        self.secret_keys.iter_mut().for_each(zeroize_cow);
+    }
+}
+
+impl<Digest, Algorithm, KeyDerivation, Encoder> fmt::Debug
+    for SignerBuilder<Digest, Algorithm, KeyDerivation, Encoder>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignerBuilder")
+            .field("digest", &any::type_name::<Digest>())
+            .field("algorithm", &any::type_name::<Algorithm>())
+            .field("key_derivation", &any::type_name::<KeyDerivation>())
+            .field("encoder", &any::type_name::<Encoder>())
+            .field("secret_keys", &RedactedKeys(self.secret_keys.len()))
+            .field("salt", &DebugSalt(&self.salt))
+            .field("separator", &self.separator)
+            .finish()
     }
 }
 
+/// A [`Signer`] which holds the derived keys, and is created using a builder.
+///
+/// The derived keys are zeroized when the signer is dropped, and are never included
+/// in its `Debug` output.
+///
+/// # Remarks
+/// The keyed state of the algorithm (e.g. the inner and outer HMAC digests) is computed once
+/// per key when the signer is built, and cloned for each signature. That state is as
+/// sensitive as the key itself, so it is also zeroized when the signer is dropped.
 pub struct SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
 where
+    Algorithm: algorithm::SigningAlgorithm,
     DerivedKeySize: ArrayLength<u8>,
 {
-    derived_key: GenericArray<u8, DerivedKeySize>,
+    derived_key: SecretKey<DerivedKeySize>,
+    /// Keys that are no longer used for signing, but are still accepted when
+    /// unsigning, ordered from newest to oldest.
+    fallback_derived_keys: Vec<SecretKey<DerivedKeySize>>,
+    /// A signer for `derived_key` that has not been given any input yet.
+    signer: SecretState<Algorithm::Signer>,
+    /// Signers for each of the `fallback_derived_keys`, in the same order.
+    fallback_signers: Vec<SecretState<Algorithm::Signer>>,
+    /// The salt the keys were derived with, which is only kept for the `Debug` impl.
+    salt: Option<Cow<'static, [u8]>>,
     pub(crate) separator: Separator,
+    /// The clock of the timestamp signer this is converted into, if one was given.
+    #[cfg(feature = "std")]
+    pub(crate) clock: Option<Box<dyn Clock>>,
     _phantom: PhantomData<(Algorithm, SignatureEncoder)>,
 }
 
+impl<Algorithm, DerivedKeySize, SignatureEncoder> fmt::Debug
+    for SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
+where
+    Algorithm: algorithm::SigningAlgorithm,
+    DerivedKeySize: ArrayLength<u8>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignerImpl")
+            .field("algorithm", &any::type_name::<Algorithm>())
+            .field("encoder", &any::type_name::<SignatureEncoder>())
+            .field(
+                "derived_keys",
+                &RedactedKeys(1 + self.fallback_derived_keys.len()),
+            )
+            .field("salt", &self.salt.as_ref().map(|salt| DebugSalt(salt)))
+            .field("separator", &self.separator)
+            .finish()
+    }
+}
+
 impl<Algorithm, DerivedKeySize, SignatureEncoder>
     SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
 where
     Algorithm: algorithm::SigningAlgorithm,
     DerivedKeySize: ArrayLength<u8>,
-    SignatureEncoder: Base64Sized,
 {
-    /// Given a base64-encoded signature, attempt to decode it and convert it
-    /// to a Signature.
-    ///
-    /// A signature is considered base64 encoded if it was encoded using
-    /// `URLSafeBase64Encode::base64_encode`.
-    #[inline(always)]
-    fn decode_signature(
-        &self,
-        encoded_signature: &[u8],
-    ) -> Result<Signature<Algorithm::OutputSize>, base64::DecodeError> {
-        Ok(base64::decode(encoded_signature)?
-            .into_exact_inner()?
-            .into())
+    pub(crate) fn with_derived_keys(
+        derived_key: SecretKey<DerivedKeySize>,
+        fallback_derived_keys: Vec<SecretKey<DerivedKeySize>>,
+        salt: Option<Cow<'static, [u8]>>,
+        separator: Separator,
+    ) -> Self {
+        let signer = SecretState::new(Algorithm::get_signer(derived_key.as_slice()));
+        let fallback_signers = fallback_derived_keys
+            .iter()
+            .map(|derived_key| SecretState::new(Algorithm::get_signer(derived_key.as_slice())))
+            .collect();
+
+        SignerImpl {
+            derived_key,
+            fallback_derived_keys,
+            signer,
+            fallback_signers,
+            salt,
+            separator,
+            #[cfg(feature = "std")]
+            clock: None,
+            _phantom: PhantomData,
+        }
     }
 
-    /// Given a signature, attempt to verify whether or not it is valid
-    /// for the given `value`.
-    #[inline(always)]
-    fn verify_signature(
-        &self,
-        value: &[u8],
-        expected_signature: Signature<Algorithm::OutputSize>,
-    ) -> bool {
-        let computed_signature = self.get_signature(value);
-        expected_signature == computed_signature
+    /// Returns the key used for signing, followed by the fallback keys from newest to oldest.
+    pub(crate) fn derived_keys(&self) -> impl Iterator<Item = &[u8]> {
+        Some(&self.derived_key)
+            .into_iter()
+            .chain(&self.fallback_derived_keys)
+            .map(SecretKey::as_slice)
     }
 }
 
@@ -126,24 +533,22 @@ impl<Algorithm, DerivedKeySize, SignatureEncoder> Signer
     for SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
 where
     Algorithm: algorithm::SigningAlgorithm,
+    Algorithm::Signer: Clone,
     DerivedKeySize: ArrayLength<u8>,
-    SignatureEncoder: Base64Sized,
+    SignatureEncoder: encoder::SignatureEncoder,
 {
     fn signature_output_size(&self) -> usize {
-        SignatureEncoder::OutputSize::USIZE
+        SignatureEncoder::encoded_len(Algorithm::OutputSize::USIZE)
     }
 
     #[inline(always)]
     fn verify_encoded_signature(&self, value: &[u8], encoded_signature: &[u8]) -> bool {
-        match self.decode_signature(encoded_signature) {
-            Ok(sig) => self.verify_signature(value, sig),
-            Err(_) => false,
-        }
+        self.verify_signature_with(encoded_signature, |signer| signer.input(value))
     }
 
     #[inline(always)]
-    fn separator(&self) -> Separator {
-        self.separator
+    fn separator(&self) -> &Separator {
+        &self.separator
     }
 
     #[inline(always)]
@@ -151,33 +556,112 @@ where
         let value = value.as_ref();
         // Pre-allocate a string with the correct size (for maximum speeds.)
         // This (albeit a bit artisnal approach) is much faster than using `format!(...)`.
-        let mut output =
-            String::with_capacity(value.len() + 1 + SignatureEncoder::OutputSize::USIZE);
+        let mut output = String::with_capacity(
+            value.len() + self.separator.as_str().len() + self.signature_output_size(),
+        );
+        self.sign_into(value, &mut output);
+        output
+    }
+
+    #[inline(always)]
+    fn sign_into<S: AsRef<str>>(&self, value: S, target: &mut String) {
+        let value = value.as_ref();
+        target.reserve(value.len() + self.separator.as_str().len() + self.signature_output_size());
 
-        output.push_str(value);
-        output.push(self.separator.0);
+        target.push_str(value);
+        target.push_str(self.separator.as_str());
         self.get_signature(value.as_bytes())
-            .base64_encode_str(&mut output);
+            .encode_str::<SignatureEncoder>(target);
+    }
 
-        output
+    fn sign_to_fmt<S, W>(&self, value: S, target: &mut W) -> fmt::Result
+    where
+        S: AsRef<str>,
+        W: ?Sized + fmt::Write,
+    {
+        let value = value.as_ref();
+        target.write_str(value)?;
+        target.write_str(self.separator.as_str())?;
+        self.get_signature(value.as_bytes())
+            .encode_fmt::<SignatureEncoder, _>(target)
+    }
+
+    fn sign_to_slice<B: AsRef<[u8]>>(
+        &self,
+        value: B,
+        target: &mut [u8],
+    ) -> Result<usize, BufferTooSmall> {
+        let value = value.as_ref();
+        let separator = self.separator.as_bytes();
+        let signature_start = value.len() + separator.len();
+        let required = signature_start + self.signature_output_size();
+        if target.len() < required {
+            return Err(BufferTooSmall {
+                required,
+                available: target.len(),
+            });
+        }
+
+        target[..value.len()].copy_from_slice(value);
+        target[value.len()..signature_start].copy_from_slice(separator);
+        let signature_length = self
+            .get_signature(value)
+            .encode_slice::<SignatureEncoder>(&mut target[signature_start..]);
+
+        Ok(signature_start + signature_length)
     }
 
     #[inline(always)]
     fn unsign<'a>(&'a self, value: &'a str) -> Result<&'a str, BadSignature<'a>> {
-        let (value, signature) = self.separator.split(&value)?;
+        let (value, signature) = self.separator.split(value)?;
         if self.verify_encoded_signature(value.as_bytes(), signature.as_bytes()) {
             Ok(value)
         } else {
             Err(BadSignature::SignatureMismatch { signature, value })
         }
     }
+
+    #[inline(always)]
+    fn sign_bytes<B: AsRef<[u8]>>(&self, value: B) -> Vec<u8> {
+        let value = value.as_ref();
+        let separator = self.separator.as_bytes();
+        let mut output =
+            Vec::with_capacity(value.len() + separator.len() + self.signature_output_size());
+
+        output.extend_from_slice(value);
+        output.extend_from_slice(separator);
+        self.get_signature(value)
+            .encode_vec::<SignatureEncoder>(&mut output);
+
+        output
+    }
+
+    #[inline(always)]
+    fn sign_detached<B: AsRef<[u8]>>(&self, value: B) -> String {
+        let mut output = String::with_capacity(self.signature_output_size());
+        self.get_signature(value.as_ref())
+            .encode_str::<SignatureEncoder>(&mut output);
+        output
+    }
+}
+
+impl<Algorithm, DerivedKeySize, SignatureEncoder> GetEncoder
+    for SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
+where
+    Algorithm: algorithm::SigningAlgorithm,
+    DerivedKeySize: ArrayLength<u8>,
+    SignatureEncoder: encoder::SignatureEncoder,
+{
+    type Encoder = SignatureEncoder;
 }
 
 impl<Algorithm, DerivedKeySize, SignatureEncoder> GetSigner
     for SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
 where
     Algorithm: algorithm::SigningAlgorithm,
+    Algorithm::Signer: Clone,
     DerivedKeySize: ArrayLength<u8>,
+    SignatureEncoder: encoder::SignatureEncoder,
 {
     type OutputSize = Algorithm::OutputSize;
     type Signer = Algorithm::Signer;
@@ -185,7 +669,52 @@ where
     /// Gets the signature for a given value.
     #[inline(always)]
     fn get_signer(&self) -> Self::Signer {
-        Self::Signer::new(self.derived_key.as_slice())
+        self.signer.get().clone()
+    }
+
+    #[inline(always)]
+    fn verify_signature_with<F>(&self, encoded_signature: &[u8], mut input: F) -> bool
+    where
+        F: FnMut(&mut Self::Signer),
+    {
+        let expected_signature = match Signature::decode::<SignatureEncoder>(encoded_signature) {
+            Some(signature) => signature,
+            None => return false,
+        };
+
+        Some(&self.signer)
+            .into_iter()
+            .chain(&self.fallback_signers)
+            .any(|signer| {
+                let mut signer = signer.get().clone();
+                input(&mut signer);
+                signer.verify(&expected_signature)
+            })
+    }
+}
+
+impl<Algorithm, DerivedKeySize, SignatureEncoder> StreamingSigner
+    for SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
+where
+    Algorithm: algorithm::SigningAlgorithm,
+    Algorithm::Signer: Clone,
+    DerivedKeySize: ArrayLength<u8>,
+    SignatureEncoder: encoder::SignatureEncoder,
+{
+    type Signer = Algorithm::Signer;
+    type Encoder = SignatureEncoder;
+
+    fn sign_stream(&self) -> SignatureStream<Self::Signer, Self::Encoder> {
+        SignatureStream::new(self.get_signer(), Vec::new())
+    }
+
+    fn verify_stream(&self) -> SignatureStream<Self::Signer, Self::Encoder> {
+        let fallback_signers = self
+            .fallback_signers
+            .iter()
+            .map(|signer| signer.get().clone())
+            .collect();
+        SignatureStream::new(self.get_signer(), fallback_signers)
     }
 }
 
@@ -193,11 +722,19 @@ impl<Algorithm, DerivedKeySize, SignatureEncoder> IntoTimestampSigner
     for SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
 where
     Algorithm: algorithm::SigningAlgorithm,
+    Algorithm::Signer: Clone,
     DerivedKeySize: ArrayLength<u8>,
-    SignatureEncoder: Base64Sized,
+    SignatureEncoder: encoder::SignatureEncoder,
 {
     type TimestampSigner = TimestampSignerImpl<Self>;
 
+    #[cfg(feature = "std")]
+    fn into_timestamp_signer(mut self) -> Self::TimestampSigner {
+        let clock = self.clock.take().unwrap_or_else(clock::default_clock);
+        TimestampSignerImpl::with_signer(self, clock)
+    }
+
+    #[cfg(not(feature = "std"))]
     fn into_timestamp_signer(self) -> Self::TimestampSigner {
         TimestampSignerImpl::with_signer(self)
     }
@@ -207,13 +744,14 @@ impl<Algorithm, DerivedKeySize, SignatureEncoder> AsSigner
     for SignerImpl<Algorithm, DerivedKeySize, SignatureEncoder>
 where
     Algorithm: algorithm::SigningAlgorithm,
+    Algorithm::Signer: Clone,
     DerivedKeySize: ArrayLength<u8>,
-    SignatureEncoder: Base64Sized,
+    SignatureEncoder: encoder::SignatureEncoder,
 {
     type Signer = Self;
 
     fn as_signer(&self) -> &Self::Signer {
-        &self
+        self
     }
 }
 
@@ -221,6 +759,8 @@ where
 mod tests {
     use super::*;
     use crate::Signer;
+    use alloc::string::ToString;
+    use alloc::{format, vec};
 
     #[test]
     fn test_signature_basic() {
@@ -247,12 +787,483 @@ mod tests {
 
     #[test]
     fn test_default_separator() {
-        assert!(!base64::in_alphabet(Separator::default().0));
+        assert!(!crate::base64::in_alphabet::<crate::encoder::Base64>(
+            Separator::default().as_str().chars().next().unwrap()
+        ));
     }
 
     #[test]
     fn test_separator_rejects_invalid_char() {
         assert!(Separator::new('a').is_err());
+        assert!(Separator::from_string("").is_err());
+        assert!(Separator::from_string(":a:").is_err());
+    }
+
+    #[test]
+    fn test_base64_profile() {
+        use crate::encoder::StandardBase64;
+
+        let signer = default_builder("hello")
+            .with_encoder(StandardBase64)
+            .build();
+        let signed = signer.sign("this is a test");
+        assert_eq!(signed, "this is a test.hgGT0Zoara4L13FX3/xm+xmfa/0=");
+        assert_eq!(signer.unsign(&signed).unwrap(), "this is a test");
+        assert!(signer
+            .unsign("this is a test.hgGT0Zoara4L13FX3/xm+xmfa/0")
+            .is_err());
+        assert!(signer
+            .unsign("this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0=")
+            .is_err());
+
+        // The standard alphabet does not contain `-`, so it can be used as a separator.
+        let separator = Separator::for_encoder::<StandardBase64>('-').unwrap();
+        let signer = default_builder("hello")
+            .with_encoder(StandardBase64)
+            .with_separator(separator)
+            .build();
+        assert_eq!(
+            signer.sign("this is a test"),
+            "this is a test-hgGT0Zoara4L13FX3/xm+xmfa/0="
+        );
+        assert!(Separator::for_encoder::<StandardBase64>('+').is_err());
+    }
+
+    #[test]
+    fn test_string_separator() {
+        use crate::encoder::Hex;
+
+        let signer = default_builder("hello")
+            .with_separator(Separator::from_string("\u{a7}\u{a7}").unwrap())
+            .build();
+        let signed = signer.sign("this is a test");
+        assert_eq!(
+            signed,
+            "this is a test\u{a7}\u{a7}hgGT0Zoara4L13FX3_xm-xmfa_0"
+        );
+        assert_eq!(signer.unsign(&signed).unwrap(), "this is a test");
+        assert_eq!(signer.sign_bytes("this is a test"), signed.as_bytes());
+        assert_eq!(
+            signer.unsign_bytes(signed.as_bytes()).unwrap(),
+            b"this is a test"
+        );
+
+        let signer = default_builder("hello")
+            .with_encoder(Hex)
+            .with_separator(Separator::string_for_encoder::<Hex, _>("::").unwrap())
+            .build();
+        let signed = signer.sign("this::is a test");
+        assert_eq!(
+            signed,
+            "this::is a test::a49fc22c33d17a5193a2a0152a578eece9a37371"
+        );
+        assert_eq!(signer.unsign(&signed).unwrap(), "this::is a test");
+    }
+
+    #[test]
+    fn test_encoders() {
+        use crate::encoder::{Base62, CrockfordBase32, Hex};
+
+        let signer = default_builder("hello").with_encoder(Hex).build();
+        let signed = signer.sign("this is a test");
+        assert_eq!(
+            signed,
+            "this is a test.860193d19a1aadae0bd77157dffc66fb199f6bfd"
+        );
+        assert_eq!(signer.signature_output_size(), 40);
+        assert_eq!(signer.unsign(&signed).unwrap(), "this is a test");
+        assert_eq!(
+            signer
+                .unsign("this is a test.860193D19A1AADAE0BD77157DFFC66FB199F6BFD")
+                .unwrap(),
+            "this is a test"
+        );
+        assert!(signer
+            .unsign("this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0")
+            .is_err());
+
+        let signer = default_builder("hello")
+            .with_encoder(CrockfordBase32)
+            .with_separator(Separator::for_encoder::<CrockfordBase32>('~').unwrap())
+            .build();
+        let signed = signer.sign("this is a test");
+        assert_eq!(signed, "this is a test~GR0S7MCT3APTW2YQE5BXZZ36ZCCSYTZX");
+        assert_eq!(
+            signer
+                .unsign("this is a test~gr0s7mct3aptw2yqe5bxzz36zccsytzx")
+                .unwrap(),
+            "this is a test"
+        );
+        assert_eq!(signer.sign_bytes("this is a test"), signed.as_bytes());
+
+        let signer = default_builder("hello").with_encoder(Base62).build();
+        let signed = signer.sign("this is a test");
+        assert_eq!(signed, "this is a test.J7TMMIk2WpryJi4ISKWRy3dx7HB");
+        assert_eq!(signer.unsign(&signed).unwrap(), "this is a test");
+        let signature = signer.sign_detached("this is a test");
+        assert!(signer
+            .verify_detached("this is a test", signature.as_str())
+            .is_ok());
+
+        let mut stream = signer.sign_stream();
+        stream.update(b"this is a test");
+        assert_eq!(stream.finish(), signature);
+        let mut stream = signer.verify_stream();
+        stream.update(b"this is a test");
+        assert!(stream.verify(signature.as_bytes()));
+    }
+
+    #[test]
+    fn test_truncated_signature() {
+        use crate::TimestampSigner;
+        use typenum::{U10, U16};
+
+        let signer = default_builder("hello")
+            .with_truncated_signature::<U10>()
+            .build();
+        let signed = signer.sign("this is a test");
+        assert_eq!(signed, "this is a test.hgGT0Zoara4L1w");
+        assert_eq!(signer.signature_output_size(), 14);
+        assert_eq!(signer.unsign(&signed).unwrap(), "this is a test");
+        assert_eq!(signer.sign_bytes("this is a test"), signed.as_bytes());
+        // The full signature, or a signature that is truncated differently, is not accepted.
+        assert!(signer
+            .unsign("this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0")
+            .is_err());
+        assert!(signer
+            .unsign("this is a test.hgGT0Zoara4L13FX3_xm-w")
+            .is_err());
+        assert!(signer.unsign("this is a test.hgGT0Zoara4L1x").is_err());
+
+        let signer = default_builder("hello")
+            .with_truncated_signature::<U16>()
+            .with_encoder(crate::encoder::Hex)
+            .build()
+            .into_timestamp_signer();
+        let timestamp = crate::time::UNIX_EPOCH + crate::time::Duration::from_secs(1560181622);
+        let signed = signer.sign_with_timestamp("this is a test", timestamp);
+        assert_eq!(signed.len(), "this is a test".len() + 1 + 6 + 1 + 32);
+        assert_eq!(signer.unsign(&signed).unwrap().value(), "this is a test");
+    }
+
+    #[test]
+    #[should_panic(expected = "the separator must not be in the alphabet of the encoder")]
+    fn test_encoder_rejects_separator_in_alphabet() {
+        let separator = Separator::for_encoder::<crate::encoder::StandardBase64>('-').unwrap();
+        default_builder("hello").with_separator(separator).build();
+    }
+
+    #[test]
+    #[should_panic(expected = "the separator must not be in the alphabet of the encoder")]
+    fn test_encoder_rejects_separator_in_timestamp_alphabet() {
+        // `-` is not a hex digit, but timestamps are still encoded using url-safe base64.
+        let separator = Separator::for_encoder::<crate::encoder::StandardBase64>('-').unwrap();
+        default_builder("hello")
+            .with_encoder(crate::encoder::Hex)
+            .with_separator(separator)
+            .build();
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let old_signer = default_builder("old key").build();
+        let signer = default_builder_with_secret_keys(vec!["old key", "hello"]).build();
+
+        // The newest key is used for signing.
+        let signature = signer.sign("this is a test");
+        assert_eq!(signature, "this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0");
+        assert_eq!(signer.unsign(&signature).unwrap(), "this is a test");
+
+        // Older keys are still accepted when unsigning.
+        let old_signature = old_signer.sign("this is a test");
+        assert_eq!(signer.unsign(&old_signature).unwrap(), "this is a test");
+        assert!(signer.verify_encoded_signature(
+            b"this is a test",
+            old_signature.rsplit('.').next().unwrap().as_bytes()
+        ));
+
+        // Keys that are not configured are not.
+        let other_signature = default_builder("other key").build().sign("this is a test");
+        assert!(signer.unsign(&other_signature).is_err());
+    }
+
+    #[test]
+    fn test_unsign_many() {
+        let signer = default_builder_with_secret_keys(vec!["old key", "hello"]).build();
+        let old_signature = default_builder("old key").build().sign("old");
+        let values = vec![
+            "this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0",
+            "this is a test.hgGT0Zoara4L13FX3_xm-xmfa_1",
+            "no separator",
+            old_signature.as_str(),
+        ];
+
+        let unsigned = signer.unsign_many(values.iter().cloned());
+        assert_eq!(unsigned.len(), 4);
+        assert_eq!(unsigned[0].as_ref().ok(), Some(&"this is a test"));
+        match unsigned[1] {
+            Err(BadSignature::SignatureMismatch { value, .. }) => {
+                assert_eq!(value, "this is a test")
+            }
+            ref other => panic!("unexpected result: {:?}", other),
+        }
+        match unsigned[2] {
+            Err(BadSignature::SeparatorNotFound { .. }) => {}
+            ref other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(unsigned[3].as_ref().ok(), Some(&"old"));
+
+        assert_eq!(signer.verify_many(values), vec![true, false, false, true]);
+        assert!(signer.unsign_many(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_unsign_many_preserves_order() {
+        let signer = default_builder("hello").build();
+        let signed: Vec<String> = (0..1000).map(|i| signer.sign(i.to_string())).collect();
+        let unsigned = signer.unsign_many(signed.iter().map(String::as_str));
+        for (i, value) in unsigned.into_iter().enumerate() {
+            assert_eq!(value.unwrap(), i.to_string());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_key_rotation_requires_a_key() {
+        default_builder_with_secret_keys(Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_sign_bytes() {
+        let signer = default_builder("hello").build();
+        let signature = signer.sign_bytes(b"this is a test");
+        assert_eq!(signature, signer.sign("this is a test").into_bytes());
+        assert_eq!(signer.unsign_bytes(&signature).unwrap(), b"this is a test");
+
+        let value = [0xff, 0x00, b'.', 0xc3, 0x28];
+        let signature = signer.sign_bytes(value);
+        assert_eq!(signer.unsign_bytes(&signature).unwrap(), &value);
+
+        let mut tampered = signature.clone();
+        tampered[0] = 0xfe;
+        assert!(signer.unsign_bytes(&tampered).is_err());
+        assert!(signer.unsign_bytes(&value).is_err());
+    }
+
+    #[test]
+    fn test_sign_detached() {
+        let signer = default_builder("hello").build();
+        let signature = signer.sign_detached("this is a test");
+        assert_eq!(signature, "hgGT0Zoara4L13FX3_xm-xmfa_0");
+        assert!(signer
+            .verify_detached("this is a test", signature.as_str())
+            .is_ok());
+        assert!(signer
+            .verify_detached(&b"this is a test"[..], signature.as_bytes())
+            .is_ok());
+
+        match signer.verify_detached("this is not a test", signature.as_str()) {
+            Err(BadSignature::SignatureMismatch { signature, value }) => {
+                assert_eq!(signature, "hgGT0Zoara4L13FX3_xm-xmfa_0");
+                assert_eq!(value, "this is not a test");
+            }
+            _ => panic!("expected a signature mismatch"),
+        }
+    }
+
+    #[test]
+    fn test_sign_into_buffers() {
+        let signer = default_builder("hello").build();
+        let expected = "this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0";
+
+        let mut target = String::from("value=");
+        signer.sign_into("this is a test", &mut target);
+        assert_eq!(target, format!("value={}", expected));
+
+        let mut target = String::new();
+        signer.sign_to_fmt("this is a test", &mut target).unwrap();
+        assert_eq!(target, expected);
+
+        let mut buffer = [0; 64];
+        let length = signer.sign_to_slice("this is a test", &mut buffer).unwrap();
+        assert_eq!(&buffer[..length], expected.as_bytes());
+        let mut exact = [0; 42];
+        assert_eq!(signer.sign_to_slice("this is a test", &mut exact), Ok(42));
+        assert_eq!(
+            signer.sign_to_slice("this is a test", &mut exact[..41]),
+            Err(BufferTooSmall {
+                required: 42,
+                available: 41
+            })
+        );
+    }
+
+    #[test]
+    fn test_sign_into_buffers_with_encoders() {
+        use crate::encoder::{Base62, CrockfordBase32, Hex, StandardBase64};
+
+        fn check<S: Signer>(signer: S) {
+            let expected = signer.sign("this is a test");
+            let mut target = String::new();
+            signer.sign_to_fmt("this is a test", &mut target).unwrap();
+            assert_eq!(target, expected);
+            let mut buffer = [0; 128];
+            let length = signer.sign_to_slice("this is a test", &mut buffer).unwrap();
+            assert_eq!(&buffer[..length], expected.as_bytes());
+        }
+
+        check(
+            default_builder("hello")
+                .with_encoder(StandardBase64)
+                .build(),
+        );
+        check(
+            default_builder("hello")
+                .with_encoder(Hex)
+                .with_separator(Separator::string_for_encoder::<Hex, _>("::").unwrap())
+                .build(),
+        );
+        check(
+            default_builder("hello")
+                .with_encoder(CrockfordBase32)
+                .build(),
+        );
+        check(default_builder("hello").with_encoder(Base62).build());
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn test_sha2_builders() {
+        // These are compatibility tests against python.
+        let signer = sha256_builder("hello").build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(
+            signature,
+            "this is a test.b4NcsM6QyyYT00kAcecRYTAuwb3-8_Sci1jm61VBWIM"
+        );
+        assert_eq!(signer.unsign(&signature).unwrap(), "this is a test");
+
+        let signer = sha512_builder("hello").build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(
+            signature,
+            "this is a test.aNrOIJeigzGyTIC42PHwZ3P7XU837rBnqk8btvVoGqlE2NvCFa0GDmTx4RvfUKuyjDgWLgiwecID5EKt3mWR0Q"
+        );
+        assert_eq!(signer.unsign(&signature).unwrap(), "this is a test");
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn test_sha3_256_builder() {
+        // This is a compatibility test against python.
+        let signer = sha3_256_builder("hello").build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(
+            signature,
+            "this is a test.zxGGNTEWl8n5PICJIiyzdhH588-4XY5pnOi_1kzgrFk"
+        );
+        assert_eq!(signer.unsign(&signature).unwrap(), "this is a test");
+    }
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn test_blake2b_builder() {
+        // This is a compatibility test against python.
+        let signer = blake2b_builder("hello").build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(
+            signature,
+            "this is a test.RI7fIOlYIUu3KE0LeSgVq1YaieU6mJiXPWtp7xkbNJIOdKE4rVfzqGCH-ccECF5JqZMJD3hz0gHJS4GktXM_Hg"
+        );
+        assert_eq!(signer.unsign(&signature).unwrap(), "this is a test");
+    }
+
+    #[test]
+    fn test_bytes_secret_key() {
+        // This is a compatibility test against python, using a bytes secret key.
+        let secret_key: Vec<u8> = (0xe0..=0xff).collect();
+        let signer = default_builder_bytes(secret_key.clone()).build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(signature, "this is a test.a19ju9hX-SgGn6427vEj5Z2-K5w");
+        assert_eq!(signer.unsign(&signature).unwrap(), "this is a test");
+
+        let signer = default_builder_bytes(secret_key)
+            .with_salt_bytes(&b"\x00salt\xff"[..])
+            .build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(signature, "this is a test.nj6k3z6J8pbGzt1frasOKirgZFU");
+    }
+
+    #[test]
+    fn test_debug_redacts_secret_keys() {
+        let builder = default_builder_with_secret_keys(vec!["hunter2", "hunter3"]);
+        let debug = format!("{:?}", builder);
+        assert!(!debug.contains("hunter"), "{}", debug);
+        assert!(debug.contains("<2 keys redacted>"), "{}", debug);
+        assert!(debug.contains("\"itsdangerous.Signer\""), "{}", debug);
+        assert!(debug.contains("DjangoConcat"), "{}", debug);
+
+        let signer = builder.build();
+        let debug = format!("{:?}", signer);
+        assert!(debug.contains("<2 keys redacted>"), "{}", debug);
+        assert!(debug.contains("HMACAlgorithm<sha1::Sha1>"), "{}", debug);
+        assert!(debug.contains("\"itsdangerous.Signer\""), "{}", debug);
+        assert!(debug.contains("Separator(\".\")"), "{}", debug);
+
+        let signer = default_builder("hello").build().into_timestamp_signer();
+        let debug = format!("{:?}", signer);
+        assert!(
+            debug.starts_with("TimestampSignerImpl(SignerImpl {"),
+            "{}",
+            debug
+        );
+        assert!(debug.contains("<1 key redacted>"), "{}", debug);
+    }
+
+    #[test]
+    fn test_bytes_secret_key_matches_str() {
+        let signer = default_builder("hello").with_salt("salt").build();
+        let bytes_signer = default_builder_bytes(&b"hello"[..])
+            .with_salt_bytes(b"salt".to_vec())
+            .build();
+        assert_eq!(
+            signer.sign("this is a test"),
+            bytes_signer.sign("this is a test")
+        );
+    }
+
+    #[cfg(feature = "hkdf")]
+    #[test]
+    fn test_hkdf_key_derivation() {
+        use crate::key_derivation::Hkdf;
+        use typenum::U32;
+
+        let signer = default_builder("hello")
+            .with_key_derivation(Hkdf::new("signing"))
+            .build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(signature, "this is a test.Z7IKN6yzz82jDwkL1WLzkBKopwI");
+        assert_eq!(signer.unsign(&signature).unwrap(), "this is a test");
+
+        let signer = default_builder("hello")
+            .with_key_derivation(Hkdf::new("signing").with_output_size::<U32>())
+            .build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(signature, "this is a test.W1finMJ73Shz2XXAYK8YUnkhIPc");
+    }
+
+    #[cfg(feature = "pbkdf2")]
+    #[test]
+    fn test_pbkdf2_key_derivation() {
+        use crate::key_derivation::Pbkdf2;
+        use core::num::NonZeroU32;
+
+        let signer = default_builder("hello")
+            .with_key_derivation(Pbkdf2::new(NonZeroU32::new(1000).unwrap()))
+            .build();
+        let signature = signer.sign("this is a test");
+        assert_eq!(signature, "this is a test.iLqiigbxVASZ2hO-7h9o5ApL1qE");
+        assert_eq!(signer.unsign(&signature).unwrap(), "this is a test");
     }
 
     #[test]
@@ -281,9 +1292,29 @@ mod bench {
         bench.iter(|| signer.unsign("this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0"))
     }
 
+    #[bench]
+    fn bench_unsign_with_fallback_key(bench: &mut Bencher) {
+        let signer = default_builder_with_secret_keys(vec!["hello", "world"]).build();
+        bench.iter(|| signer.unsign("this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0"))
+    }
+
+    #[bench]
+    fn bench_unsign_many(bench: &mut Bencher) {
+        let signer = default_builder("hello").build();
+        let values = vec!["this is a test.hgGT0Zoara4L13FX3_xm-xmfa_0"; 1000];
+        bench.iter(|| signer.unsign_many(values.iter().cloned()))
+    }
+
     #[bench]
     fn bench_sign(bench: &mut Bencher) {
         let signer = default_builder("hello").build();
         bench.iter(|| signer.sign("this is a test"))
     }
+
+    #[cfg(feature = "sha2")]
+    #[bench]
+    fn bench_sign_sha512(bench: &mut Bencher) {
+        let signer = sha512_builder("hello").build();
+        bench.iter(|| signer.sign("this is a test"))
+    }
 }
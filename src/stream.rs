@@ -0,0 +1,197 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::algorithm::{Signature, Signer as AlgorithmSigner};
+use crate::encoder::{Base64, SignatureEncoder};
+
+/// Incrementally computes the signature of a value that is provided in chunks, for example
+/// from an [`io::Read`](std::io::Read), so that the value never has to be held in memory all at once.
+///
+/// A [`SignatureStream`] is created using [`StreamingSigner::sign_stream`] or
+/// [`StreamingSigner::verify_stream`]. Chunks are appended using [`update`], or by using
+/// the stream as an [`io::Write`](std::io::Write). Once all chunks have been appended, the stream can
+/// either produce a detached signature, encoded using the signer's encoder, using [`finish`],
+/// or verify one using [`verify`].
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// use std::io::Cursor;
+/// use itsdangerous::{default_builder, Signer, StreamingSigner};
+///
+/// let signer = default_builder("secret key").build();
+///
+/// let mut stream = signer.sign_stream();
+/// stream.update_from_reader(Cursor::new("a very large file")).unwrap();
+/// let signature = stream.finish();
+///
+/// let mut stream = signer.verify_stream();
+/// stream.update(b"a very large ");
+/// stream.update(b"file");
+/// assert!(stream.verify(signature.as_bytes()));
+/// # }
+/// ```
+///
+/// [`StreamingSigner::sign_stream`]: crate::StreamingSigner::sign_stream
+/// [`StreamingSigner::verify_stream`]: crate::StreamingSigner::verify_stream
+/// [`update`]: SignatureStream::update
+/// [`finish`]: SignatureStream::finish
+/// [`verify`]: SignatureStream::verify
+pub struct SignatureStream<TSigner, Encoder = Base64> {
+    signer: TSigner,
+    fallback_signers: Vec<TSigner>,
+    _phantom: PhantomData<Encoder>,
+}
+
+impl<TSigner, Encoder> SignatureStream<TSigner, Encoder>
+where
+    TSigner: AlgorithmSigner,
+    Encoder: SignatureEncoder,
+{
+    pub(crate) fn new(signer: TSigner, fallback_signers: Vec<TSigner>) -> Self {
+        Self {
+            signer,
+            fallback_signers,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Appends a chunk of the value to the stream.
+    #[inline(always)]
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.signer.input(chunk);
+        for signer in &mut self.fallback_signers {
+            signer.input(chunk);
+        }
+    }
+
+    /// Reads `reader` until it is exhausted, appending everything that was read to
+    /// the stream. Returns the number of bytes that were read. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn update_from_reader<R: io::Read>(&mut self, mut reader: R) -> io::Result<u64> {
+        io::copy(&mut reader, self)
+    }
+
+    /// Consumes the stream, returning the encoded signature of everything
+    /// that was appended to it.
+    ///
+    /// This is the signature part that [`Signer::sign`] would have emitted after the
+    /// separator, had it been given the entire value at once.
+    ///
+    /// [`Signer::sign`]: crate::Signer::sign
+    pub fn finish(self) -> String {
+        let mut output = String::new();
+        self.signer.sign().encode_str::<Encoder>(&mut output);
+        output
+    }
+
+    /// Consumes the stream, verifying that `encoded_signature` is a valid encoded
+    /// signature of everything that was appended to it.
+    pub fn verify(self, encoded_signature: &[u8]) -> bool {
+        let expected_signature = match Signature::decode::<Encoder>(encoded_signature) {
+            Some(signature) => signature,
+            None => return false,
+        };
+
+        if self.signer.verify(&expected_signature) {
+            return true;
+        }
+
+        self.fallback_signers
+            .into_iter()
+            .any(|signer| signer.verify(&expected_signature))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<TSigner, Encoder> io::Write for SignatureStream<TSigner, Encoder>
+where
+    TSigner: AlgorithmSigner,
+    Encoder: SignatureEncoder,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::{default_builder, default_builder_with_secret_keys, Signer, StreamingSigner};
+
+    #[test]
+    fn test_sign_stream() {
+        let signer = default_builder("hello").build();
+        let mut stream = signer.sign_stream();
+        stream.update(b"this is ");
+        stream.update(b"a test");
+        assert_eq!(stream.finish(), "hgGT0Zoara4L13FX3_xm-xmfa_0");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_stream_io() {
+        use std::io::{Cursor, Write};
+
+        let signer = default_builder("hello").build();
+        let mut stream = signer.sign_stream();
+        stream.update(b"this is ");
+        stream.write_all(b"a test").unwrap();
+        assert_eq!(stream.finish(), "hgGT0Zoara4L13FX3_xm-xmfa_0");
+
+        let value = vec![7u8; 1 << 20];
+        let signed = signer.sign_bytes(&value);
+        let mut stream = signer.verify_stream();
+        let read = stream.update_from_reader(Cursor::new(&value)).unwrap();
+        assert_eq!(read, value.len() as u64);
+        assert!(stream.verify(&signed[value.len() + 1..]));
+    }
+
+    #[test]
+    fn test_verify_stream() {
+        let signer = default_builder("hello").build();
+        let value = vec![7u8; 1 << 20];
+        let signed = signer.sign_bytes(&value);
+        let signature = &signed[value.len() + 1..];
+
+        let mut stream = signer.verify_stream();
+        stream.update(&value);
+        assert!(stream.verify(signature));
+
+        let mut stream = signer.verify_stream();
+        stream.update(&value[1..]);
+        assert!(!stream.verify(signature));
+
+        let mut stream = signer.verify_stream();
+        stream.update(&value);
+        assert!(!stream.verify(b"not a signature"));
+    }
+
+    #[test]
+    fn test_verify_stream_with_rotated_key() {
+        let old_signer = default_builder("old key").build();
+        let signer = default_builder_with_secret_keys(vec!["old key", "hello"]).build();
+
+        let mut stream = old_signer.sign_stream();
+        stream.update(b"this is a test");
+        let signature = stream.finish();
+
+        let mut stream = signer.verify_stream();
+        stream.update(b"this is a test");
+        assert!(stream.verify(signature.as_bytes()));
+
+        // Streams that are only used for signing do not consider fallback keys.
+        let mut stream = signer.sign_stream();
+        stream.update(b"this is a test");
+        assert!(!stream.verify(signature.as_bytes()));
+    }
+}
@@ -0,0 +1,98 @@
+//! The time types used by timed signers.
+//!
+//! With the `std` feature (the default), these are re-exports of [`std::time`]. Without it,
+//! there is no system clock, so [`SystemTime`] is a minimal replacement which can only be
+//! created from a caller-supplied offset from the [`UNIX_EPOCH`], and which has the subset of
+//! the [`std::time::SystemTime`] API that timed signers use.
+//!
+//! # Example
+//! ```rust
+//! use itsdangerous::time::{Duration, UNIX_EPOCH};
+//! use itsdangerous::{default_builder, IntoTimestampSigner, TimestampSigner};
+//!
+//! let signer = default_builder("secret key").build().into_timestamp_signer();
+//! let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+//! let signed = signer.sign_with_timestamp("hello world!", timestamp);
+//! assert_eq!(signer.unsign(&signed).unwrap().timestamp(), timestamp);
+//! ```
+//!
+//! [`std::time`]: https://doc.rust-lang.org/std/time/index.html
+//! [`std::time::SystemTime`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html
+
+pub use core::time::Duration;
+
+#[cfg(feature = "std")]
+pub use std::time::{SystemTime, SystemTimeError, UNIX_EPOCH};
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std::{SystemTime, SystemTimeError, UNIX_EPOCH};
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use core::fmt;
+    use core::ops::{Add, Sub};
+    use core::time::Duration;
+
+    /// A point in time, stored as an offset from the [`UNIX_EPOCH`].
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+    pub struct SystemTime(Duration);
+
+    /// The start of unix time, 1970-01-01 00:00:00 UTC.
+    pub const UNIX_EPOCH: SystemTime = SystemTime(Duration::from_secs(0));
+
+    /// Error returned from [`SystemTime::duration_since`] if the earlier time is later.
+    #[derive(Clone, Debug)]
+    pub struct SystemTimeError(Duration);
+
+    impl SystemTime {
+        /// Returns the amount of time elapsed from `earlier` to this time, or an error if
+        /// `earlier` is later than this time.
+        pub fn duration_since(&self, earlier: SystemTime) -> Result<Duration, SystemTimeError> {
+            self.0
+                .checked_sub(earlier.0)
+                .ok_or_else(|| SystemTimeError(earlier.0 - self.0))
+        }
+
+        /// Returns `Some(t)` where `t` is this time plus `duration`, or `None` on overflow.
+        pub fn checked_add(&self, duration: Duration) -> Option<SystemTime> {
+            self.0.checked_add(duration).map(SystemTime)
+        }
+
+        /// Returns `Some(t)` where `t` is this time minus `duration`, or `None` if `t` would
+        /// be before the [`UNIX_EPOCH`].
+        pub fn checked_sub(&self, duration: Duration) -> Option<SystemTime> {
+            self.0.checked_sub(duration).map(SystemTime)
+        }
+    }
+
+    impl Add<Duration> for SystemTime {
+        type Output = SystemTime;
+
+        fn add(self, duration: Duration) -> SystemTime {
+            self.checked_add(duration)
+                .expect("overflow when adding duration to instant")
+        }
+    }
+
+    impl Sub<Duration> for SystemTime {
+        type Output = SystemTime;
+
+        fn sub(self, duration: Duration) -> SystemTime {
+            self.checked_sub(duration)
+                .expect("overflow when subtracting duration from instant")
+        }
+    }
+
+    impl SystemTimeError {
+        /// Returns how far the earlier time was after the later one.
+        pub fn duration(&self) -> Duration {
+            self.0
+        }
+    }
+
+    impl fmt::Display for SystemTimeError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("second time provided was later than self")
+        }
+    }
+}
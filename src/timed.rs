@@ -1,20 +1,138 @@
-use std::time::{Duration, SystemTime};
+use crate::time::{Duration, SystemTime};
+use alloc::borrow::ToOwned;
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
 
-use crate::algorithm::Signer as AlgorithmSigner;
-use crate::base64::URLSafeBase64Encode;
-use crate::error::BadTimedSignature;
+use crate::algorithm::{Signature, Signer as AlgorithmSigner};
+#[cfg(feature = "std")]
+use crate::clock::{self, Clock};
+use crate::encoder::{Base64Profile, SignatureEncoder};
+use crate::error::{BadTimedSignature, BufferTooSmall};
+#[cfg(feature = "std")]
+use crate::expiry::ExpiryPolicy;
 use crate::timestamp;
-use crate::traits::GetSigner;
-use crate::{AsSigner, Separator, Signer, TimestampSigner};
+use crate::traits::{GetEncoder, GetSigner};
+use crate::{AsSigner, Separator, Signer, TimestampSigner, TimestampVerifier, Verifier};
 
-pub struct TimestampSignerImpl<TSigner>(TSigner);
+/// Passes each chunk of the signed part of a value + encoded timestamp to `input`.
+#[inline(always)]
+fn input_timestamped_value<F: FnMut(&[u8])>(
+    separator: &Separator,
+    value: &[u8],
+    encoded_timestamp: &[u8],
+    mut input: F,
+) {
+    input(value);
+    input(separator.as_bytes());
+    input(encoded_timestamp);
+}
+
+/// Appended to the signed part of a token signed with an expiry when computing its signature,
+/// so that tokens and values signed with a timestamp cannot be unsigned as each other. The part
+/// after the last separator of a value signed with a timestamp is always an encoded timestamp,
+/// which is shorter than this, and separators are never alphanumeric.
+const EXPIRING_TOKEN_DOMAIN: &[u8] = b"itsdangerousExpiringToken";
+
+/// Passes each chunk of the signed part of an expiring token + [`EXPIRING_TOKEN_DOMAIN`] to
+/// `input`.
+#[inline(always)]
+fn input_expiring_token<F: FnMut(&[u8])>(separator: &Separator, token: &[u8], mut input: F) {
+    input(token);
+    input(separator.as_bytes());
+    input(EXPIRING_TOKEN_DOMAIN);
+}
+
+/// The base64 profile that the timestamps of a [`TimestampSignerImpl`] or
+/// [`TimestampVerifierImpl`] are encoded with.
+type TimestampProfile<T> = <<T as GetEncoder>::Encoder as SignatureEncoder>::TimestampProfile;
+
+pub struct TimestampSignerImpl<TSigner> {
+    signer: TSigner,
+    #[cfg(feature = "std")]
+    clock: Box<dyn Clock>,
+    #[cfg(feature = "std")]
+    expiry_policy: Option<ExpiryPolicy>,
+}
 
 impl<TSigner> TimestampSignerImpl<TSigner>
 where
     TSigner: Signer + GetSigner,
 {
-    pub(crate) fn with_signer(signer: TSigner) -> Self {
-        Self(signer)
+    /// The builders check that the separator of `signer` is not in the base64 alphabet
+    /// which timestamps are encoded with.
+    pub(crate) fn with_signer(
+        signer: TSigner,
+        #[cfg(feature = "std")] clock: Box<dyn Clock>,
+    ) -> Self {
+        debug_assert!(signer.separator().is_valid_for::<TSigner::Encoder>());
+        Self {
+            signer,
+            #[cfg(feature = "std")]
+            clock,
+            #[cfg(feature = "std")]
+            expiry_policy: None,
+        }
+    }
+
+    /// Uses a specific clock, instead of the one given to the builder, see
+    /// [`SignerBuilder::with_clock`]. Requires the `std` feature.
+    ///
+    /// [`SignerBuilder::with_clock`]: crate::SignerBuilder::with_clock
+    #[cfg(feature = "std")]
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Enforces `expiry_policy` when unsigning values, see [`ExpiryPolicy`]. Requires the `std`
+    /// feature.
+    #[cfg(feature = "std")]
+    pub fn with_expiry_policy(mut self, expiry_policy: ExpiryPolicy) -> Self {
+        self.expiry_policy = Some(expiry_policy);
+        self
+    }
+
+    fn sign_with_validity_period(
+        &self,
+        value: &str,
+        not_before: Option<SystemTime>,
+        expires_at: SystemTime,
+    ) -> String {
+        let not_before = not_before.map(timestamp::encode::<TimestampProfile<TSigner>>);
+        let expires_at = timestamp::encode::<TimestampProfile<TSigner>>(expires_at);
+        let separator = self.signer.separator().as_str();
+
+        // An empty `not_before` means that the token is valid until it expires.
+        let mut output = String::with_capacity(
+            value.len()
+                + separator.len()
+                + not_before
+                    .as_ref()
+                    .map_or(0, |not_before| not_before.length())
+                + separator.len()
+                + expires_at.length()
+                + separator.len()
+                + self.signer.signature_output_size(),
+        );
+        output.push_str(value);
+        output.push_str(separator);
+        if let Some(not_before) = &not_before {
+            output.push_str(not_before.as_str());
+        }
+        output.push_str(separator);
+        output.push_str(expires_at.as_str());
+
+        let mut signer = self.signer.get_signer();
+        input_expiring_token(self.signer.separator(), output.as_bytes(), |chunk| {
+            signer.input(chunk)
+        });
+        let signature = signer.sign();
+        output.push_str(separator);
+        signature.encode_str::<<TSigner as GetEncoder>::Encoder>(&mut output);
+        output
     }
 
     pub(crate) fn split<'a>(
@@ -22,53 +140,153 @@ where
         value: &'a str,
     ) -> Result<(&'a str, &'a str), BadTimedSignature<'a>> {
         // Then we split it again, to extract the value & timestamp.
-        self.0
+        self.signer
             .separator()
             .split(value)
             .map_err(|_| BadTimedSignature::TimestampMissing { value })
     }
+
+    /// Checks the `timestamp` of an unsigned `value` against the expiry policy, if there is one.
+    fn unsigned_value<'a, V: ?Sized>(
+        &'a self,
+        value: &'a V,
+        timestamp: SystemTime,
+    ) -> Result<UnsignedValue<'a, V>, BadTimedSignature<'a, V>> {
+        #[cfg(feature = "std")]
+        return UnsignedValue::new(
+            value,
+            timestamp,
+            self.clock.now(),
+            self.expiry_policy.as_ref(),
+        );
+        #[cfg(not(feature = "std"))]
+        return Ok(UnsignedValue::new(value, timestamp));
+    }
+
+    /// Returns the signature for a given value + encoded timestamp.
+    #[inline(always)]
+    fn get_timestamp_signature(
+        &self,
+        value: &[u8],
+        encoded_timestamp: &[u8],
+    ) -> Signature<<TSigner as GetSigner>::OutputSize> {
+        let mut signer = self.signer.get_signer();
+        input_timestamped_value(self.signer.separator(), value, encoded_timestamp, |chunk| {
+            signer.input(chunk)
+        });
+        signer.sign()
+    }
 }
 
 impl<TSigner> TimestampSigner for TimestampSignerImpl<TSigner>
 where
     TSigner: Signer + GetSigner,
 {
-    fn separator(&self) -> Separator {
-        self.0.separator()
+    fn separator(&self) -> &Separator {
+        self.signer.separator()
+    }
+
+    #[cfg(feature = "std")]
+    fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
+
+    #[cfg(feature = "std")]
+    fn expiry_policy(&self) -> Option<&ExpiryPolicy> {
+        self.expiry_policy.as_ref()
     }
 
     /// Signs a value with an arbitrary timestamp.
     fn sign_with_timestamp<S: AsRef<str>>(&self, value: S, timestamp: SystemTime) -> String {
+        // `sign_with_timestamp_into` reserves exactly the length of the signed value.
+        let mut output = String::new();
+        self.sign_with_timestamp_into(value, timestamp, &mut output);
+        output
+    }
+
+    fn sign_with_timestamp_into<S: AsRef<str>>(
+        &self,
+        value: S,
+        timestamp: SystemTime,
+        target: &mut String,
+    ) {
         let value = value.as_ref();
-        let encoded_timestamp = timestamp::encode(timestamp);
-        let separator = self.0.separator().0;
+        let encoded_timestamp = timestamp::encode::<TimestampProfile<TSigner>>(timestamp);
+        let separator = self.signer.separator().as_str();
 
         // Generate the signature.
-        let signature = self
-            .0
-            .get_signer()
-            .input_chained(value.as_bytes())
-            .input_chained(&[separator as u8])
-            .input_chained(encoded_timestamp.as_slice())
-            .sign();
+        let signature =
+            self.get_timestamp_signature(value.as_bytes(), encoded_timestamp.as_slice());
 
         // Generate the signed output string.
-        let mut output = String::with_capacity(
-            value.len() + 1 + encoded_timestamp.length() + 1 + self.0.signature_output_size(),
+        target.reserve(
+            value.len()
+                + separator.len()
+                + encoded_timestamp.length()
+                + separator.len()
+                + self.signer.signature_output_size(),
         );
+        target.push_str(value);
+        target.push_str(separator);
+        target.push_str(encoded_timestamp.as_str());
+        target.push_str(separator);
+        signature.encode_str::<<TSigner as GetEncoder>::Encoder>(target);
+    }
 
-        output.push_str(value);
-        output.push(separator);
-        output.push_str(encoded_timestamp.as_str());
-        output.push(separator);
-        signature.base64_encode_str(&mut output);
+    fn sign_with_timestamp_to_fmt<S, W>(
+        &self,
+        value: S,
+        timestamp: SystemTime,
+        target: &mut W,
+    ) -> fmt::Result
+    where
+        S: AsRef<str>,
+        W: ?Sized + fmt::Write,
+    {
+        let value = value.as_ref();
+        let encoded_timestamp = timestamp::encode::<TimestampProfile<TSigner>>(timestamp);
+        let separator = self.signer.separator().as_str();
+        let signature =
+            self.get_timestamp_signature(value.as_bytes(), encoded_timestamp.as_slice());
 
-        output
+        target.write_str(value)?;
+        target.write_str(separator)?;
+        target.write_str(encoded_timestamp.as_str())?;
+        target.write_str(separator)?;
+        signature.encode_fmt::<<TSigner as GetEncoder>::Encoder, _>(target)
     }
 
-    /// Signs a value using the current system timestamp (as provided by [`SystemTime::now`]).
-    fn sign<S: AsRef<str>>(&self, value: S) -> String {
-        self.sign_with_timestamp(value, SystemTime::now())
+    fn sign_with_timestamp_to_slice<B: AsRef<[u8]>>(
+        &self,
+        value: B,
+        timestamp: SystemTime,
+        target: &mut [u8],
+    ) -> Result<usize, BufferTooSmall> {
+        let value = value.as_ref();
+        let encoded_timestamp = timestamp::encode::<TimestampProfile<TSigner>>(timestamp);
+        let separator = self.signer.separator().as_bytes();
+
+        let required = value.len()
+            + separator.len()
+            + encoded_timestamp.length()
+            + separator.len()
+            + self.signer.signature_output_size();
+        if target.len() < required {
+            return Err(BufferTooSmall {
+                required,
+                available: target.len(),
+            });
+        }
+
+        let signature = self.get_timestamp_signature(value, encoded_timestamp.as_slice());
+        let mut length = 0;
+        for chunk in &[value, separator, encoded_timestamp.as_slice(), separator] {
+            target[length..length + chunk.len()].copy_from_slice(chunk);
+            length += chunk.len();
+        }
+        length += signature.encode_slice::<<TSigner as GetEncoder>::Encoder>(&mut target[length..]);
+
+        Ok(length)
     }
 
     /// The inverse of [`sign`] / [`sign_with_timestamp`], returning an [`UnsignedValue`], which you
@@ -83,13 +301,136 @@ where
     /// [`&str`]: std::str
     /// [`sign`]: TimestampSigner::sign
     /// [`sign_with_timestamp`]: TimestampSigner::sign_with_timestamp
-    fn unsign<'a>(&'a self, value: &'a str) -> Result<UnsignedValue, BadTimedSignature<'a>> {
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<UnsignedValue<'a>, BadTimedSignature<'a>> {
         // The base unsigner gives us {value}{sep}{timestamp}.
-        let value = self.0.unsign(value)?;
+        let value = self.signer.unsign(value)?;
         let (value, timestamp) = self.split(value)?;
-        let timestamp = timestamp::decode(timestamp)?;
+        let timestamp = timestamp::decode::<TimestampProfile<TSigner>, _>(timestamp)?;
+
+        self.unsigned_value(value, timestamp)
+    }
+
+    fn sign_bytes_with_timestamp<B: AsRef<[u8]>>(
+        &self,
+        value: B,
+        timestamp: SystemTime,
+    ) -> Vec<u8> {
+        let value = value.as_ref();
+        let encoded_timestamp = timestamp::encode::<TimestampProfile<TSigner>>(timestamp);
+        let signature = self.get_timestamp_signature(value, encoded_timestamp.as_slice());
+
+        let separator = self.signer.separator().as_bytes();
+        let mut output = Vec::with_capacity(
+            value.len()
+                + separator.len()
+                + encoded_timestamp.length()
+                + separator.len()
+                + self.signer.signature_output_size(),
+        );
+
+        output.extend_from_slice(value);
+        output.extend_from_slice(separator);
+        output.extend_from_slice(encoded_timestamp.as_slice());
+        output.extend_from_slice(separator);
+        signature.encode_vec::<<TSigner as GetEncoder>::Encoder>(&mut output);
+
+        output
+    }
+
+    fn unsign_bytes<'a>(
+        &'a self,
+        value: &'a [u8],
+    ) -> Result<UnsignedValue<'a, [u8]>, BadTimedSignature<'a, [u8]>> {
+        let value = self.signer.unsign_bytes(value)?;
+        let (value, timestamp) = self
+            .signer
+            .separator()
+            .split_bytes(value)
+            .map_err(|_| BadTimedSignature::TimestampMissing { value })?;
+        let timestamp = timestamp::decode::<TimestampProfile<TSigner>, _>(timestamp)?;
+
+        self.unsigned_value(value, timestamp)
+    }
+
+    fn sign_detached_with_timestamp<B: AsRef<[u8]>>(
+        &self,
+        value: B,
+        timestamp: SystemTime,
+    ) -> (String, String) {
+        let encoded_timestamp = timestamp::encode::<TimestampProfile<TSigner>>(timestamp);
+        let signature = self.get_timestamp_signature(value.as_ref(), encoded_timestamp.as_slice());
+
+        let mut output = String::with_capacity(self.signer.signature_output_size());
+        signature.encode_str::<<TSigner as GetEncoder>::Encoder>(&mut output);
 
-        Ok(UnsignedValue { timestamp, value })
+        (encoded_timestamp.as_str().to_owned(), output)
+    }
+
+    fn verify_detached<'a, V>(
+        &'a self,
+        value: &'a V,
+        timestamp: &'a V,
+        signature: &'a V,
+    ) -> Result<UnsignedValue<'a, V>, BadTimedSignature<'a, V>>
+    where
+        V: ?Sized + AsRef<[u8]>,
+    {
+        let verified = self
+            .signer
+            .verify_signature_with(signature.as_ref(), |signer| {
+                input_timestamped_value(
+                    self.signer.separator(),
+                    value.as_ref(),
+                    timestamp.as_ref(),
+                    |chunk| signer.input(chunk),
+                )
+            });
+        if !verified {
+            return Err(BadTimedSignature::SignatureMismatch { signature, value });
+        }
+
+        let timestamp = timestamp::decode::<TimestampProfile<TSigner>, _>(timestamp)?;
+        self.unsigned_value(value, timestamp)
+    }
+
+    fn sign_with_expiry<S: AsRef<str>>(&self, value: S, expires_at: SystemTime) -> String {
+        self.sign_with_validity_period(value.as_ref(), None, expires_at)
+    }
+
+    fn sign_with_validity<S: AsRef<str>>(
+        &self,
+        value: S,
+        not_before: SystemTime,
+        expires_at: SystemTime,
+    ) -> String {
+        self.sign_with_validity_period(value.as_ref(), Some(not_before), expires_at)
+    }
+
+    fn unsign_with_expiry_at<'a>(
+        &'a self,
+        value: &'a str,
+        now: SystemTime,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>> {
+        let separator = self.signer.separator();
+        let (value, signature) = separator.split(value)?;
+        let verified = self
+            .signer
+            .verify_signature_with(signature.as_bytes(), |signer| {
+                input_expiring_token(separator, value.as_bytes(), |chunk| signer.input(chunk))
+            });
+        if !verified {
+            return Err(BadTimedSignature::SignatureMismatch { signature, value });
+        }
+
+        ExpiringValue::new::<TimestampProfile<TSigner>>(separator, value, now)
+    }
+}
+
+impl<TSigner: fmt::Debug> fmt::Debug for TimestampSignerImpl<TSigner> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TimestampSignerImpl")
+            .field(&self.signer)
+            .finish()
     }
 }
 
@@ -100,23 +441,273 @@ where
     type Signer = TSigner;
 
     fn as_signer(&self) -> &Self::Signer {
-        &self.0
+        &self.signer
+    }
+}
+
+/// A [`TimestampVerifier`] which wraps a [`Verifier`], and is created using
+/// [`IntoTimestampVerifier::into_timestamp_verifier`].
+///
+/// [`IntoTimestampVerifier::into_timestamp_verifier`]: crate::IntoTimestampVerifier::into_timestamp_verifier
+pub struct TimestampVerifierImpl<TVerifier> {
+    verifier: TVerifier,
+    #[cfg(feature = "std")]
+    clock: Box<dyn Clock>,
+    #[cfg(feature = "std")]
+    expiry_policy: Option<ExpiryPolicy>,
+}
+
+impl<TVerifier> TimestampVerifierImpl<TVerifier>
+where
+    TVerifier: Verifier + GetEncoder,
+{
+    pub(crate) fn with_verifier(verifier: TVerifier) -> Self {
+        Self {
+            verifier,
+            #[cfg(feature = "std")]
+            clock: clock::default_clock(),
+            #[cfg(feature = "std")]
+            expiry_policy: None,
+        }
+    }
+
+    /// Uses a specific clock, instead of the system clock. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Enforces `expiry_policy` when unsigning values, see [`ExpiryPolicy`]. Requires the `std`
+    /// feature.
+    #[cfg(feature = "std")]
+    pub fn with_expiry_policy(mut self, expiry_policy: ExpiryPolicy) -> Self {
+        self.expiry_policy = Some(expiry_policy);
+        self
+    }
+
+    /// See [`TimestampSignerImpl::unsigned_value`].
+    fn unsigned_value<'a, V: ?Sized>(
+        &'a self,
+        value: &'a V,
+        timestamp: SystemTime,
+    ) -> Result<UnsignedValue<'a, V>, BadTimedSignature<'a, V>> {
+        #[cfg(feature = "std")]
+        return UnsignedValue::new(
+            value,
+            timestamp,
+            self.clock.now(),
+            self.expiry_policy.as_ref(),
+        );
+        #[cfg(not(feature = "std"))]
+        return Ok(UnsignedValue::new(value, timestamp));
+    }
+}
+
+impl<TVerifier: fmt::Debug> fmt::Debug for TimestampVerifierImpl<TVerifier> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TimestampVerifierImpl")
+            .field(&self.verifier)
+            .finish()
     }
 }
 
-/// Represents a value + timestamp that has been successfully unsigned by [`TimestampSigner::unsign`].
-pub struct UnsignedValue<'a> {
+impl<TVerifier> TimestampVerifier for TimestampVerifierImpl<TVerifier>
+where
+    TVerifier: Verifier + GetEncoder,
+{
+    fn separator(&self) -> &Separator {
+        self.verifier.separator()
+    }
+
+    #[cfg(feature = "std")]
+    fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
+
+    #[cfg(feature = "std")]
+    fn expiry_policy(&self) -> Option<&ExpiryPolicy> {
+        self.expiry_policy.as_ref()
+    }
+
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<UnsignedValue<'a>, BadTimedSignature<'a>> {
+        let value = self.verifier.unsign(value)?;
+        let (value, timestamp) = self
+            .verifier
+            .separator()
+            .split(value)
+            .map_err(|_| BadTimedSignature::TimestampMissing { value })?;
+        let timestamp = timestamp::decode::<TimestampProfile<TVerifier>, _>(timestamp)?;
+
+        self.unsigned_value(value, timestamp)
+    }
+
+    fn unsign_bytes<'a>(
+        &'a self,
+        value: &'a [u8],
+    ) -> Result<UnsignedValue<'a, [u8]>, BadTimedSignature<'a, [u8]>> {
+        let value = self.verifier.unsign_bytes(value)?;
+        let (value, timestamp) = self
+            .verifier
+            .separator()
+            .split_bytes(value)
+            .map_err(|_| BadTimedSignature::TimestampMissing { value })?;
+        let timestamp = timestamp::decode::<TimestampProfile<TVerifier>, _>(timestamp)?;
+
+        self.unsigned_value(value, timestamp)
+    }
+
+    fn verify_detached<'a, V>(
+        &'a self,
+        value: &'a V,
+        timestamp: &'a V,
+        signature: &'a V,
+    ) -> Result<UnsignedValue<'a, V>, BadTimedSignature<'a, V>>
+    where
+        V: ?Sized + AsRef<[u8]>,
+    {
+        let mut signed_value = Vec::new();
+        input_timestamped_value(
+            self.verifier.separator(),
+            value.as_ref(),
+            timestamp.as_ref(),
+            |chunk| signed_value.extend_from_slice(chunk),
+        );
+        if !self
+            .verifier
+            .verify_encoded_signature(&signed_value, signature.as_ref())
+        {
+            return Err(BadTimedSignature::SignatureMismatch { signature, value });
+        }
+
+        let timestamp = timestamp::decode::<TimestampProfile<TVerifier>, _>(timestamp)?;
+        self.unsigned_value(value, timestamp)
+    }
+
+    fn unsign_with_expiry_at<'a>(
+        &'a self,
+        value: &'a str,
+        now: SystemTime,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>> {
+        let separator = self.verifier.separator();
+        let (value, signature) = separator.split(value)?;
+        let mut token = Vec::new();
+        input_expiring_token(separator, value.as_bytes(), |chunk| {
+            token.extend_from_slice(chunk)
+        });
+        if !self
+            .verifier
+            .verify_encoded_signature(&token, signature.as_bytes())
+        {
+            return Err(BadTimedSignature::SignatureMismatch { signature, value });
+        }
+
+        ExpiringValue::new::<TimestampProfile<TVerifier>>(separator, value, now)
+    }
+}
+
+/// Represents a token that has been successfully unsigned by
+/// [`TimestampSigner::unsign_with_expiry`], and which is valid at the time it was unsigned.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ExpiringValue<'a> {
     value: &'a str,
+    expires_at: SystemTime,
+    not_before: Option<SystemTime>,
+}
+
+impl<'a> ExpiringValue<'a> {
+    /// Splits the `not_before` and `expires_at` from an unsigned `value`, and checks them
+    /// against the time `now`.
+    fn new<P: Base64Profile>(
+        separator: &Separator,
+        value: &'a str,
+        now: SystemTime,
+    ) -> Result<Self, BadTimedSignature<'a>> {
+        let (value, expires_at) = separator
+            .split(value)
+            .map_err(|_| BadTimedSignature::TimestampMissing { value })?;
+        let (value, not_before) = separator
+            .split(value)
+            .map_err(|_| BadTimedSignature::TimestampMissing { value })?;
+        let expires_at = timestamp::decode::<P, _>(expires_at)?;
+        let not_before = match not_before {
+            "" => None,
+            not_before => Some(timestamp::decode::<P, _>(not_before)?),
+        };
+
+        if now >= expires_at {
+            return Err(BadTimedSignature::TokenExpired { expires_at, value });
+        }
+        match not_before {
+            Some(not_before) if now < not_before => {
+                Err(BadTimedSignature::TokenNotYetValid { not_before, value })
+            }
+            _ => Ok(ExpiringValue {
+                value,
+                expires_at,
+                not_before,
+            }),
+        }
+    }
+
+    /// The value that has been unsigned.
+    pub fn value(&self) -> &'a str {
+        self.value
+    }
+
+    /// The time that the token expires at.
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+
+    /// The time that the token is valid from, if it was signed with one.
+    pub fn not_before(&self) -> Option<SystemTime> {
+        self.not_before
+    }
+}
+
+/// Represents a value + timestamp that has been successfully unsigned by [`TimestampSigner::unsign`]
+/// or [`TimestampSigner::unsign_bytes`].
+pub struct UnsignedValue<'a, V: ?Sized = str> {
+    value: &'a V,
     timestamp: SystemTime,
+    /// The current time of the signer's clock when the value was unsigned.
+    #[cfg(feature = "std")]
+    now: SystemTime,
 }
 
-impl<'a> UnsignedValue<'a> {
+impl<'a, V: ?Sized> UnsignedValue<'a, V> {
+    /// Checks the timestamp of `value` against `expiry_policy` at the time `now`, if there is
+    /// one.
+    #[cfg(feature = "std")]
+    pub(crate) fn new(
+        value: &'a V,
+        timestamp: SystemTime,
+        now: SystemTime,
+        expiry_policy: Option<&ExpiryPolicy>,
+    ) -> Result<Self, BadTimedSignature<'a, V>> {
+        if let Some(expiry_policy) = expiry_policy {
+            expiry_policy.check(now, timestamp, value)?;
+        }
+
+        Ok(UnsignedValue {
+            value,
+            timestamp,
+            now,
+        })
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn new(value: &'a V, timestamp: SystemTime) -> Self {
+        UnsignedValue { value, timestamp }
+    }
+
     /// The value that has been [`unsigned`]. This value is safe to use and
     /// was part of a payload that has been successfully [`unsigned`].
     ///
     /// [`unsigned`]: TimestampSigner::unsign
-    pub fn value(&self) -> &'a str {
-        &self.value
+    pub fn value(&self) -> &'a V {
+        self.value
     }
 
     /// The timestamp that the value was signed with.
@@ -129,13 +720,37 @@ impl<'a> UnsignedValue<'a> {
         self.timestamp
     }
 
-    /// Returns the value if the timestamp is not older than `max_age`.
+    /// Returns the value if the timestamp was not older than `max_age` when the value was
+    /// unsigned, according to the [`Clock`] of the signer that unsigned it. The time is read
+    /// once when unsigning, so the answer does not change if the clock moves afterwards.
     /// In the event that the timestamp is in the future, we'll consider that valid.
     ///
     /// If the value is expired, returns the [`BadTimedSignature::TimestampExpired`]
     /// vairant of [`BadTimedSignature`].
-    pub fn value_if_not_expired(self, max_age: Duration) -> Result<&'a str, BadTimedSignature<'a>> {
-        match self.timestamp.elapsed() {
+    ///
+    /// To also reject timestamps in the future, or to have every value checked when it is
+    /// unsigned, use an [`ExpiryPolicy`] instead. Requires the `std` feature, see
+    /// [`value_if_not_expired_at`] otherwise.
+    ///
+    /// [`value_if_not_expired_at`]: UnsignedValue::value_if_not_expired_at
+    #[cfg(feature = "std")]
+    pub fn value_if_not_expired(
+        self,
+        max_age: Duration,
+    ) -> Result<&'a V, BadTimedSignature<'a, V>> {
+        let now = self.now;
+        self.value_if_not_expired_at(now, max_age)
+    }
+
+    /// Returns the value if the timestamp is not older than `max_age` at the time `now`,
+    /// which is supplied by the caller.
+    /// In the event that the timestamp is in the future, we'll consider that valid.
+    pub fn value_if_not_expired_at(
+        self,
+        now: SystemTime,
+        max_age: Duration,
+    ) -> Result<&'a V, BadTimedSignature<'a, V>> {
+        match now.duration_since(self.timestamp) {
             Ok(duration) if duration > max_age => Err(BadTimedSignature::TimestampExpired {
                 timestamp: self.timestamp,
                 value: self.value,
@@ -149,8 +764,15 @@ impl<'a> UnsignedValue<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{default_builder, IntoTimestampSigner, TimestampSigner};
-    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use crate::time::{Duration, UNIX_EPOCH};
+    use crate::{
+        default_builder, default_builder_with_secret_keys, AsSigner, IntoTimestampSigner,
+        Separator, Signer, TimestampSigner,
+    };
+    use alloc::borrow::ToOwned;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use alloc::{format, vec};
 
     #[test]
     fn test_sign() {
@@ -164,10 +786,568 @@ mod tests {
         assert_eq!(unsigned.timestamp(), timestamp);
     }
 
+    #[test]
+    fn test_unsign_with_rotated_key() {
+        let old_signer = default_builder("old key").build().into_timestamp_signer();
+        let signer = default_builder_with_secret_keys(vec!["old key", "hello"])
+            .build()
+            .into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+        assert_eq!(signed, "hello world.XP57dg.uBK_KvrfABr48ZHk6IrBINjpqp8");
+
+        let signed = old_signer.sign_with_timestamp("hello world", timestamp);
+        let unsigned = signer.unsign(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.timestamp(), timestamp);
+    }
+
+    #[test]
+    fn test_sign_bytes() {
+        let signer = default_builder("hello").build().into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signed = signer.sign_bytes_with_timestamp(b"hello world", timestamp);
+
+        assert_eq!(signed, b"hello world.XP57dg.uBK_KvrfABr48ZHk6IrBINjpqp8");
+        let unsigned = signer.unsign_bytes(&signed).unwrap();
+        assert_eq!(unsigned.value(), b"hello world");
+        assert_eq!(unsigned.timestamp(), timestamp);
+
+        #[cfg(feature = "std")]
+        {
+            let value = [0xff, 0x00, b'.', 0xc3, 0x28];
+            let signed = signer.sign_bytes(value);
+            let unsigned = signer.unsign_bytes(&signed).unwrap();
+            assert_eq!(unsigned.value(), &value);
+            assert!(unsigned
+                .value_if_not_expired(Duration::from_secs(60))
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_sign_detached() {
+        let signer = default_builder("hello").build().into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let (encoded_timestamp, signature) =
+            signer.sign_detached_with_timestamp("hello world", timestamp);
+
+        assert_eq!(encoded_timestamp, "XP57dg");
+        assert_eq!(signature, "uBK_KvrfABr48ZHk6IrBINjpqp8");
+        let unsigned = signer
+            .verify_detached("hello world", "XP57dg", "uBK_KvrfABr48ZHk6IrBINjpqp8")
+            .unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.timestamp(), timestamp);
+
+        assert!(signer
+            .verify_detached("hello world", "XP57dh", "uBK_KvrfABr48ZHk6IrBINjpqp8")
+            .is_err());
+        assert!(signer
+            .verify_detached("hello worle", "XP57dg", "uBK_KvrfABr48ZHk6IrBINjpqp8")
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_detached_with_rotated_key() {
+        let old_signer = default_builder("old key").build().into_timestamp_signer();
+        let signer = default_builder_with_secret_keys(vec!["old key", "hello"])
+            .build()
+            .into_timestamp_signer();
+
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let (timestamp, signature) =
+            old_signer.sign_detached_with_timestamp(b"hello world", timestamp);
+        let unsigned = signer
+            .verify_detached(
+                &b"hello world"[..],
+                timestamp.as_bytes(),
+                signature.as_bytes(),
+            )
+            .unwrap();
+        assert_eq!(unsigned.value(), b"hello world");
+    }
+
+    #[test]
+    fn test_sign_with_encoder() {
+        use crate::encoder::Base62;
+
+        let signer = default_builder("hello")
+            .with_encoder(Base62)
+            .build()
+            .into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+        let (rest, signature) = signed.rsplit_once('.').unwrap();
+        assert_eq!(rest, "hello world.XP57dg");
+        assert_eq!(signature.len(), 27);
+        assert!(signature.chars().all(|c| c.is_ascii_alphanumeric()));
+
+        let unsigned = signer.unsign(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.timestamp(), timestamp);
+
+        let (encoded_timestamp, signature) =
+            signer.sign_detached_with_timestamp("hello world", timestamp);
+        assert!(signer
+            .verify_detached(
+                "hello world",
+                encoded_timestamp.as_str(),
+                signature.as_str()
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_sign_with_base64_profile() {
+        use crate::encoder::StandardBase64;
+
+        let signer = default_builder("hello")
+            .with_encoder(StandardBase64)
+            .build()
+            .into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+
+        assert_eq!(signed, "hello world.XP57dg==.i2nlkXXu27vpdDvmVCkiPhkounk=");
+        let unsigned = signer.unsign(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.timestamp(), timestamp);
+    }
+
+    #[test]
+    fn test_sign_into_buffers() {
+        use crate::encoder::StandardBase64;
+        use crate::BufferTooSmall;
+
+        let signer = default_builder("hello").build().into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let expected = "hello world.XP57dg.uBK_KvrfABr48ZHk6IrBINjpqp8";
+
+        let mut target = String::from("value=");
+        signer.sign_with_timestamp_into("hello world", timestamp, &mut target);
+        assert_eq!(target, format!("value={}", expected));
+
+        let mut target = String::new();
+        signer
+            .sign_with_timestamp_to_fmt("hello world", timestamp, &mut target)
+            .unwrap();
+        assert_eq!(target, expected);
+
+        let mut buffer = [0; 46];
+        let length = signer
+            .sign_with_timestamp_to_slice("hello world", timestamp, &mut buffer)
+            .unwrap();
+        assert_eq!(&buffer[..length], expected.as_bytes());
+        assert_eq!(
+            signer.sign_with_timestamp_to_slice("hello world", timestamp, &mut buffer[..45]),
+            Err(BufferTooSmall {
+                required: 46,
+                available: 45
+            })
+        );
+
+        let signer = default_builder("hello")
+            .with_encoder(StandardBase64)
+            .build()
+            .into_timestamp_signer();
+        let length = signer
+            .sign_with_timestamp_to_slice("hello world", timestamp, &mut [0; 64])
+            .unwrap();
+        assert_eq!(
+            length,
+            "hello world.XP57dg==.i2nlkXXu27vpdDvmVCkiPhkounk=".len()
+        );
+    }
+
+    #[test]
+    fn test_encoder_separator_in_base64_alphabet() {
+        use crate::encoder::{Hex, StandardBase64};
+
+        // Timestamps of hex signers are encoded using url-safe base64.
+        assert!(Separator::for_encoder::<Hex>('_').is_err());
+        assert!(Separator::for_encoder::<Hex>('-').is_err());
+        let signer = default_builder("hello")
+            .with_encoder(Hex)
+            .with_separator(Separator::for_encoder::<Hex>(':').unwrap())
+            .build()
+            .into_timestamp_signer();
+        let signed = signer.sign_with_timestamp("hello", UNIX_EPOCH);
+        assert_eq!(signer.unsign(&signed).unwrap().value(), "hello");
+
+        // Timestamps of standard base64 signers use the standard alphabet.
+        assert!(Separator::for_encoder::<StandardBase64>('/').is_err());
+        let signer = default_builder("hello")
+            .with_encoder(StandardBase64)
+            .with_separator(Separator::for_encoder::<StandardBase64>('-').unwrap())
+            .build()
+            .into_timestamp_signer();
+        let signed = signer.sign_with_timestamp("hello", UNIX_EPOCH);
+        assert_eq!(signer.unsign(&signed).unwrap().value(), "hello");
+    }
+
+    #[test]
+    fn test_multi_byte_separators() {
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let plain_signer = default_builder("hello").build();
+
+        for separator in &["\u{a7}", "::", "\u{2014}|\u{2014}"] {
+            let signer = default_builder("hello")
+                .with_separator(Separator::from_string(*separator).unwrap())
+                .build()
+                .into_timestamp_signer();
+            let signed = signer.sign_with_timestamp("hello world", timestamp);
+
+            // The signature must cover the separator exactly as it appears in the output.
+            let timestamped_value = format!("hello world{}XP57dg", separator);
+            let plain_signed = plain_signer.sign(&timestamped_value);
+            let expected_signature = &plain_signed[timestamped_value.len() + 1..];
+            assert_eq!(
+                signed,
+                format!("{}{}{}", timestamped_value, separator, expected_signature)
+            );
+
+            let unsigned = signer.unsign(&signed).unwrap();
+            assert_eq!(unsigned.value(), "hello world");
+            assert_eq!(unsigned.timestamp(), timestamp);
+
+            let signed_bytes = signer.sign_bytes_with_timestamp("hello world", timestamp);
+            assert_eq!(signed_bytes, signed.as_bytes());
+            assert_eq!(
+                signer.unsign_bytes(&signed_bytes).unwrap().value(),
+                b"hello world"
+            );
+
+            let (encoded_timestamp, signature) =
+                signer.sign_detached_with_timestamp("hello world", timestamp);
+            assert_eq!(signature, expected_signature);
+            assert!(signer
+                .verify_detached(
+                    "hello world",
+                    encoded_timestamp.as_str(),
+                    signature.as_str()
+                )
+                .is_ok());
+
+            let tampered = signed.replacen(separator, ".", 1);
+            assert!(signer.unsign(&tampered).is_err());
+        }
+    }
+
+    #[test]
+    fn test_value_if_not_expired_at() {
+        let signer = default_builder("hello").build().into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+        let unsigned = || signer.unsign(&signed).unwrap();
+
+        let now = timestamp + Duration::from_secs(60);
+        assert!(unsigned()
+            .value_if_not_expired_at(now, Duration::from_secs(30))
+            .is_err());
+        assert_eq!(
+            unsigned()
+                .value_if_not_expired_at(now, Duration::from_secs(60))
+                .unwrap(),
+            "hello world"
+        );
+        // Timestamps in the future are considered valid.
+        assert!(unsigned()
+            .value_if_not_expired_at(UNIX_EPOCH, Duration::from_secs(0))
+            .is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_expiry_policy() {
+        use crate::clock::FixedClock;
+        use crate::{ExpiryPolicy, OwnedBadTimedSignature};
+
+        let now = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signer = default_builder("hello")
+            .with_clock(FixedClock(now))
+            .build()
+            .into_timestamp_signer();
+        let expired = signer.sign_with_timestamp("hello world", now - Duration::from_secs(61));
+        let in_future = signer.sign_with_timestamp("hello world", now + Duration::from_secs(10));
+
+        // Without a policy, the age of values is not checked.
+        assert!(signer.unsign(&expired).is_ok());
+        assert!(signer.unsign(&in_future).is_ok());
+
+        let signer = signer.with_expiry_policy(
+            ExpiryPolicy::new()
+                .with_max_age(Duration::from_secs(60))
+                .with_max_future_skew(Duration::from_secs(5)),
+        );
+        let error = signer.unsign(&expired).err().unwrap();
+        assert!(error.is_expired());
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Timestamp {:?} is older than 60s and is expired.",
+                now - Duration::from_secs(61)
+            )
+        );
+        let error = signer.unsign_bytes(in_future.as_bytes()).err().unwrap();
+        assert!(error.is_in_future());
+        assert_eq!(error.code(), "timestamp_in_future");
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Timestamp {:?} is more than 5s in the future.",
+                now + Duration::from_secs(10)
+            )
+        );
+        let error: OwnedBadTimedSignature<Vec<u8>> = error.into();
+        assert!(error.is_in_future() && !error.is_malformed());
+
+        let (timestamp, signature) =
+            signer.sign_detached_with_timestamp("hello world", now + Duration::from_secs(5));
+        assert!(signer
+            .verify_detached("hello world", timestamp.as_str(), signature.as_str())
+            .is_ok());
+
+        let signer =
+            signer.with_expiry_policy(ExpiryPolicy::new().with_min_age(Duration::from_secs(30)));
+        let signed = signer.sign("hello world");
+        let error = signer.unsign(&signed).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Timestamp {:?} is less than 30s old, allowing for 0ns of clock skew.",
+                now
+            )
+        );
+        assert!(signer.unsign(&expired).is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sign_with_expiry() {
+        use crate::clock::ManualClock;
+        use crate::OwnedBadTimedSignature;
+
+        let now = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let clock = ManualClock::new(now);
+        let signer = default_builder("hello")
+            .with_clock(clock.clone())
+            .build()
+            .into_timestamp_signer();
+
+        let signed = signer.sign_with_expiry("hello world", now);
+        assert!(signed.starts_with("hello world..XP57dg."), "{}", signed);
+        let error = signer.unsign_with_expiry(&signed).err().unwrap();
+        assert!(error.is_expired());
+        assert_eq!(error.code(), "token_expired");
+        assert_eq!(error.to_string(), format!("Token expired at {:?}.", now));
+
+        let signed = signer.sign_with_expiry("hello.world", now + Duration::from_secs(60));
+        let unsigned = signer.unsign_with_expiry(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello.world");
+        assert_eq!(unsigned.expires_at(), now + Duration::from_secs(60));
+        assert_eq!(unsigned.not_before(), None);
+
+        let not_before = now + Duration::from_secs(30);
+        let signed =
+            signer.sign_with_validity("hello world", not_before, now + Duration::from_secs(60));
+        let error = signer.unsign_with_expiry(&signed).err().unwrap();
+        assert!(error.is_in_future());
+        assert_eq!(error.code(), "token_not_yet_valid");
+        let error: OwnedBadTimedSignature = error.into();
+        assert_eq!(
+            error.to_string(),
+            format!("Token is not valid before {:?}.", not_before)
+        );
+
+        clock.advance(Duration::from_secs(30));
+        let unsigned = signer.unsign_with_expiry(&signed).unwrap();
+        assert_eq!(unsigned.value(), "hello world");
+        assert_eq!(unsigned.not_before(), Some(not_before));
+        clock.advance(Duration::from_secs(30));
+        assert!(signer
+            .unsign_with_expiry(&signed)
+            .err()
+            .unwrap()
+            .is_expired());
+
+        // Values signed with a timestamp are not tokens.
+        let signed = signer.sign("hello world");
+        assert!(signer
+            .unsign_with_expiry(&signed)
+            .err()
+            .unwrap()
+            .is_tampered());
+    }
+
+    #[test]
+    fn test_expiring_tokens_are_not_timestamped_values() {
+        let now = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let expires_at = now + Duration::from_secs(60);
+        let signer = default_builder("hello").build().into_timestamp_signer();
+
+        // The signed part of a token, `{value}{sep}{not_before}{sep}{expires_at}`, looks like
+        // a value signed with a timestamp, whose value is `{value}{sep}{not_before}`.
+        let token = signer.sign_with_validity("hello", now, expires_at);
+        assert!(token.starts_with("hello.XP57dg."), "{}", token);
+        let unsigned = signer.unsign_with_expiry_at(&token, now).unwrap();
+        assert_eq!(unsigned.value(), "hello");
+        assert_eq!(
+            signer.unsign(&token).err().unwrap().code(),
+            "signature_mismatch"
+        );
+        assert!(signer.as_signer().unsign(&token).is_err());
+
+        // The other way around, a value signed with a timestamp which contains the separator
+        // looks like a token.
+        let signed = signer.sign_with_timestamp("hello.XP57dg", expires_at);
+        assert_eq!(signer.unsign(&signed).unwrap().value(), "hello.XP57dg");
+        assert_eq!(
+            signer
+                .unsign_with_expiry_at(&signed, now)
+                .err()
+                .unwrap()
+                .code(),
+            "signature_mismatch"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sign_with_clock() {
+        use crate::clock::{FixedClock, ManualClock};
+
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let clock = ManualClock::new(timestamp);
+        let signer = default_builder("hello")
+            .with_clock(clock.clone())
+            .build()
+            .into_timestamp_signer();
+
+        let signed = signer.sign("hello world");
+        assert_eq!(signed, "hello world.XP57dg.uBK_KvrfABr48ZHk6IrBINjpqp8");
+        assert_eq!(signer.sign_bytes("hello world"), signed.as_bytes());
+
+        clock.advance(Duration::from_secs(60));
+        assert!(signer
+            .unsign(&signed)
+            .unwrap()
+            .value_if_not_expired(Duration::from_secs(60))
+            .is_ok());
+        clock.advance(Duration::from_secs(1));
+        assert!(signer
+            .unsign(&signed)
+            .unwrap()
+            .value_if_not_expired(Duration::from_secs(60))
+            .is_err());
+
+        // The clock of a timestamp signer can also be replaced after it is built.
+        let signer = signer.with_clock(FixedClock(timestamp));
+        assert_eq!(signer.sign("hello world"), signed);
+        assert!(signer
+            .unsign(&signed)
+            .unwrap()
+            .value_if_not_expired(Duration::from_secs(0))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_into_owned_error() {
+        use crate::{BadTimedSignature, OwnedBadTimedSignature};
+
+        fn assert_static_error<E: core::error::Error + Send + Sync + 'static>(_: &E) {}
+
+        let signer = default_builder("hello").build().into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+        let error = signer
+            .unsign(&signed)
+            .unwrap()
+            .value_if_not_expired_at(timestamp + Duration::from_secs(61), Duration::from_secs(60))
+            .unwrap_err();
+        let display = error.to_string();
+
+        let owned = error.into_owned();
+        assert_static_error(&owned);
+        assert_eq!(owned.to_string(), display);
+        match owned {
+            OwnedBadTimedSignature::TimestampExpired { value, .. } => {
+                assert_eq!(value, "hello world")
+            }
+            _ => panic!("expected an expired timestamp"),
+        }
+
+        let unsign = |value: &str| -> Result<String, OwnedBadTimedSignature> {
+            Ok(signer.unsign(value)?.value().to_owned())
+        };
+        assert_eq!(unsign(&signed).unwrap(), "hello world");
+        match unsign(&signed.replace("hello", "howdy")) {
+            Err(OwnedBadTimedSignature::SignatureMismatch { value, .. }) => {
+                assert!(value.starts_with("howdy world."))
+            }
+            _ => panic!("expected a signature mismatch"),
+        }
+
+        let owned: OwnedBadTimedSignature<Vec<u8>> = BadTimedSignature::TimestampMissing {
+            value: &b"hello"[..],
+        }
+        .into();
+        assert_static_error(&owned);
+    }
+
+    #[test]
+    fn test_error_classification() {
+        let signer = default_builder("hello").build().into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let signed = signer.sign_with_timestamp("hello world", timestamp);
+
+        let error = signer.unsign("hello world").err().unwrap();
+        assert!(error.is_malformed() && !error.is_tampered() && !error.is_expired());
+        assert_eq!(error.code(), "separator_not_found");
+
+        let tampered = signed.replace("hello", "howdy");
+        let error = signer.unsign(&tampered).err().unwrap();
+        assert!(error.is_tampered() && !error.is_malformed() && !error.is_expired());
+        assert_eq!(error.code(), "signature_mismatch");
+
+        let no_timestamp = default_builder("hello").build().sign("hello world");
+        let error = signer.unsign(&no_timestamp).err().unwrap();
+        assert!(error.is_malformed());
+        assert_eq!(error.code(), "timestamp_missing");
+
+        let error = signer
+            .unsign(&signed)
+            .unwrap()
+            .value_if_not_expired_at(timestamp + Duration::from_secs(61), Duration::from_secs(60))
+            .unwrap_err();
+        assert!(error.is_expired() && !error.is_tampered() && !error.is_malformed());
+        assert_eq!(error.code(), "timestamp_expired");
+        assert_eq!(error.into_owned().code(), "timestamp_expired");
+    }
+
+    #[test]
+    fn test_unsign_many() {
+        let signer = default_builder("hello").build().into_timestamp_signer();
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+        let values = [
+            signer.sign_with_timestamp("hello world", timestamp),
+            "hello world.D-AM9g.T7AHtE1DsJn4dzUb-oeOwpWWoX9".to_owned(),
+        ];
+
+        let unsigned = signer.unsign_many(values.iter().map(String::as_str));
+        assert_eq!(unsigned.len(), 2);
+        let first = unsigned[0].as_ref().unwrap();
+        assert_eq!(first.value(), "hello world");
+        assert_eq!(first.timestamp(), timestamp);
+        assert!(unsigned[1].is_err());
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_sign_expired() {
         let signer = default_builder("hello").build().into_timestamp_signer();
-        let timestamp = SystemTime::now() - Duration::from_secs(60);
+        let timestamp = crate::time::SystemTime::now() - Duration::from_secs(60);
         let signed = signer.sign_with_timestamp("hello world", timestamp);
         let unsigned = signer.unsign(&signed).unwrap();
 
@@ -175,10 +1355,11 @@ mod tests {
             .value_if_not_expired(Duration::from_secs(30))
             .is_err());
     }
+    #[cfg(feature = "std")]
     #[test]
     fn test_sign_not_expired() {
         let signer = default_builder("hello").build().into_timestamp_signer();
-        let timestamp = SystemTime::now() - Duration::from_secs(60);
+        let timestamp = crate::time::SystemTime::now() - Duration::from_secs(60);
         let signed = signer.sign_with_timestamp("hello world", timestamp);
         let unsigned = signer.unsign(&signed).unwrap();
 
@@ -1,10 +1,10 @@
-use std::mem;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use generic_array::{self, ArrayLength, GenericArray};
-use typenum::{Unsigned, U8};
+use typenum::{Add1, Unsigned, U8};
 
 use crate::base64::{self, Base64Sized, Base64SizedEncoder};
+use crate::encoder::Base64Profile;
 use crate::error::BadTimedSignature;
 
 pub(crate) struct EncodedTimestamp<N: ArrayLength<u8>> {
@@ -26,18 +26,20 @@ impl<N: ArrayLength<u8>> EncodedTimestamp<N> {
     #[inline(always)]
     pub(crate) fn as_str(&self) -> &str {
         // This is safe, because we know that an encoded timestamp's bytes
-        // are within the url-safe base64 alphabet, which is plain ascii,
+        // are within a base64 alphabet, which is plain ascii,
         // and totally fine to coerce to utf8.
-        unsafe { std::str::from_utf8_unchecked(self.as_slice()) }
+        unsafe { core::str::from_utf8_unchecked(self.as_slice()) }
     }
 }
 
 type TimestampEncoder = Base64SizedEncoder<U8>;
+/// The largest encoded timestamp, which has a single byte of padding if the profile is padded.
+type EncodedTimestampSize = Add1<<TimestampEncoder as Base64Sized>::OutputSize>;
 
 #[inline(always)]
-pub(crate) fn encode(
+pub(crate) fn encode<P: Base64Profile>(
     timestamp: SystemTime,
-) -> EncodedTimestamp<<TimestampEncoder as Base64Sized>::OutputSize> {
+) -> EncodedTimestamp<EncodedTimestampSize> {
     type InputSize = <TimestampEncoder as Base64Sized>::InputSize;
     // This is compatible with itsdangerous 1.x, which is what we're using in prod right now.
     let epoch_delta: u64 = timestamp.duration_since(UNIX_EPOCH).unwrap().as_secs();
@@ -45,7 +47,7 @@ pub(crate) fn encode(
     // Fastest transform + strip + encode in the west.
     // - The nice thing is that this is compile time checked to be a sane transformation, e.g.,
     //   if TimestampEncoder was initialized using say a <U9>, the code just wouldn't compile!
-    let timestamp_bytes: [u8; InputSize::USIZE] = unsafe { mem::transmute(epoch_delta.to_be()) };
+    let timestamp_bytes: [u8; InputSize::USIZE] = epoch_delta.to_be_bytes();
 
     // We need to strip the leading zero bytes, to do that, we take the leading
     // zeroes, and count em.
@@ -53,16 +55,20 @@ pub(crate) fn encode(
 
     // Finally, we can do the encoding.
     let mut array = GenericArray::default();
-    let length = base64::encode_slice(&timestamp_bytes[zero_index..], array.as_mut_slice());
+    let length = base64::encode_slice::<P, _>(&timestamp_bytes[zero_index..], array.as_mut_slice());
     EncodedTimestamp { array, length }
 }
 
 #[inline(always)]
-pub(crate) fn decode(timestamp: &str) -> Result<SystemTime, BadTimedSignature> {
+pub(crate) fn decode<P, V>(timestamp: &V) -> Result<SystemTime, BadTimedSignature<'_, V>>
+where
+    P: Base64Profile,
+    V: ?Sized + AsRef<[u8]>,
+{
     type InputSize = <TimestampEncoder as Base64Sized>::InputSize;
 
     // Decode the base-64 encoded timestamp to bytes.
-    let timestamp_bytes = base64::decode::<InputSize, _>(timestamp)
+    let timestamp_bytes = base64::decode::<P, InputSize, _>(timestamp)
         .map_err(|_| BadTimedSignature::TimestampInvalid { timestamp })?;
 
     let timestamp_bytes = timestamp_bytes.as_slice();
@@ -78,5 +84,5 @@ pub(crate) fn decode(timestamp: &str) -> Result<SystemTime, BadTimedSignature> {
     // Convert from timestamp to a SystemTime - handle the overflow by returning TimestampInvalid.
     UNIX_EPOCH
         .checked_add(timestamp_duration)
-        .ok_or_else(|| BadTimedSignature::TimestampInvalid { timestamp })
+        .ok_or(BadTimedSignature::TimestampInvalid { timestamp })
 }
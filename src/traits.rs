@@ -1,11 +1,20 @@
-use std::time::SystemTime;
+use crate::time::SystemTime;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
 
 use generic_array::ArrayLength;
 use typenum::Unsigned;
 
 use crate::algorithm::{Signature, Signer as AlgorithmSigner};
-use crate::error::BadSignature;
-use crate::{BadTimedSignature, Separator, UnsignedValue};
+use crate::batch;
+#[cfg(feature = "std")]
+use crate::clock::{Clock, SystemClock};
+use crate::encoder::SignatureEncoder;
+use crate::error::{BadSignature, BufferTooSmall};
+#[cfg(feature = "std")]
+use crate::ExpiryPolicy;
+use crate::{BadTimedSignature, ExpiringValue, Separator, SignatureStream, UnsignedValue};
 
 /// A signer can sign and unsign bytes, validating the signature provided.
 ///
@@ -32,6 +41,68 @@ pub trait Signer {
     /// Signs the given string.
     fn sign<S: AsRef<str>>(&self, value: S) -> String;
 
+    /// Signs the given string, appending the signed value to `target` rather than allocating
+    /// a new [`String`]. `target` is only reallocated if it does not have enough spare
+    /// capacity, so re-using it across calls avoids allocating at all.
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::{default_builder, Signer};
+    ///
+    /// let signer = default_builder("secret key").build();
+    /// let mut signed = String::with_capacity(64);
+    /// for value in &["hello", "world"] {
+    ///     signed.clear();
+    ///     signer.sign_into(value, &mut signed);
+    ///     assert_eq!(signer.unsign(&signed).unwrap(), *value);
+    /// }
+    /// ```
+    fn sign_into<S: AsRef<str>>(&self, value: S, target: &mut String);
+
+    /// Signs the given string, writing the signed value to `target` without allocating.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::fmt::Write;
+    /// use itsdangerous::{default_builder, Signer};
+    ///
+    /// let signer = default_builder("secret key").build();
+    /// let mut header = String::from("token=");
+    /// signer.sign_to_fmt("hello", &mut header).unwrap();
+    /// write!(header, "; Path=/").unwrap();
+    /// assert_eq!(header, "token=hello.Cihu8w4HvBqXWE64zLZ9utErhLA; Path=/");
+    /// ```
+    fn sign_to_fmt<S, W>(&self, value: S, target: &mut W) -> fmt::Result
+    where
+        S: AsRef<str>,
+        W: ?Sized + fmt::Write;
+
+    /// Signs the given bytes into the start of `target` without allocating, returning the
+    /// length of the signed value, which is the same as the output of [`sign_bytes`].
+    ///
+    /// # Errors
+    /// Returns [`BufferTooSmall`] if `target` is shorter than the value, the separator and
+    /// [`signature_output_size`] combined, in which case `target` is left untouched.
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::{default_builder, Signer};
+    ///
+    /// let signer = default_builder("secret key").build();
+    /// let mut buffer = [0; 64];
+    /// let length = signer.sign_to_slice("hello", &mut buffer).unwrap();
+    /// assert_eq!(&buffer[..length], b"hello.Cihu8w4HvBqXWE64zLZ9utErhLA");
+    /// assert!(signer.sign_to_slice("hello", &mut buffer[..8]).is_err());
+    /// ```
+    ///
+    /// [`sign_bytes`]: Signer::sign_bytes
+    /// [`signature_output_size`]: Signer::signature_output_size
+    fn sign_to_slice<B: AsRef<[u8]>>(
+        &self,
+        value: B,
+        target: &mut [u8],
+    ) -> Result<usize, BufferTooSmall>;
+
     /// Unsigns the given string. The logical inverse of [`sign`].
     ///
     /// # Remarks
@@ -44,7 +115,123 @@ pub trait Signer {
     /// [`sign`]: Signer::sign
     fn unsign<'a>(&'a self, value: &'a str) -> Result<&'a str, BadSignature<'a>>;
 
-    fn separator(&self) -> Separator;
+    /// Signs the given bytes.
+    ///
+    /// This produces the same output as [`sign`] if the bytes are valid utf-8.
+    ///
+    /// [`sign`]: Signer::sign
+    fn sign_bytes<B: AsRef<[u8]>>(&self, value: B) -> Vec<u8>;
+
+    /// Unsigns the given bytes. The logical inverse of [`sign_bytes`].
+    ///
+    /// # Remarks
+    ///
+    /// Like [`unsign`], this method performs zero copies or heap allocations and returns
+    /// a reference to a slice of the provided `value`.
+    ///
+    /// [`sign_bytes`]: Signer::sign_bytes
+    /// [`unsign`]: Signer::unsign
+    fn unsign_bytes<'a>(&'a self, value: &'a [u8]) -> Result<&'a [u8], BadSignature<'a, [u8]>> {
+        let (value, signature) = self.separator().split_bytes(value)?;
+        if self.verify_encoded_signature(value, signature) {
+            Ok(value)
+        } else {
+            Err(BadSignature::SignatureMismatch { signature, value })
+        }
+    }
+
+    /// Unsigns each of the given strings, returning the results in the same order.
+    ///
+    /// # Remarks
+    ///
+    /// The key state is shared between all of the values. If the `rayon` feature is enabled,
+    /// the values are unsigned in parallel.
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::{default_builder, Signer};
+    ///
+    /// let signer = default_builder("secret key").build();
+    /// let signed = vec![signer.sign("hello"), "world.tampered".to_owned()];
+    ///
+    /// let unsigned = signer.unsign_many(signed.iter().map(String::as_str));
+    /// assert_eq!(unsigned[0].as_ref().ok(), Some(&"hello"));
+    /// assert!(unsigned[1].is_err());
+    /// ```
+    fn unsign_many<'a, I>(&'a self, values: I) -> Vec<Result<&'a str, BadSignature<'a>>>
+    where
+        Self: Sync,
+        I: IntoIterator<Item = &'a str>,
+    {
+        batch::map_in_order(values, |value| self.unsign(value))
+    }
+
+    /// Returns whether each of the given strings has a valid signature, in the same order.
+    ///
+    /// Like [`unsign_many`], the values are verified in parallel if the `rayon` feature
+    /// is enabled.
+    ///
+    /// [`unsign_many`]: Signer::unsign_many
+    fn verify_many<'a, I>(&'a self, values: I) -> Vec<bool>
+    where
+        Self: Sync,
+        I: IntoIterator<Item = &'a str>,
+    {
+        batch::map_in_order(values, |value| self.unsign(value).is_ok())
+    }
+
+    /// Signs the given value, returning only the encoded signature, rather than
+    /// `value + separator + signature`. This is useful when the value is stored or
+    /// transmitted separately from its signature.
+    ///
+    /// The default implementation takes the signature from the output of [`sign_bytes`],
+    /// which must be `{value}{sep}{signature}`.
+    ///
+    /// # Panics
+    /// Panics if the output of [`sign_bytes`] does not have that layout, or if the signature
+    /// in it is not valid utf-8.
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::{default_builder, Signer};
+    ///
+    /// let signer = default_builder("secret key").build();
+    /// let signature = signer.sign_detached("hello world!");
+    /// assert!(signer.verify_detached("hello world!", signature.as_str()).is_ok());
+    /// assert!(signer.verify_detached("goodbye world!", signature.as_str()).is_err());
+    /// ```
+    ///
+    /// [`sign_bytes`]: Signer::sign_bytes
+    fn sign_detached<B: AsRef<[u8]>>(&self, value: B) -> String {
+        const LAYOUT: &str = "`sign_bytes` must return `{value}{sep}{signature}`";
+
+        let value = value.as_ref();
+        let signed = self.sign_bytes(value);
+        let signature = signed
+            .get(value.len() + self.separator().as_bytes().len()..)
+            .expect(LAYOUT);
+        String::from_utf8(signature.to_vec()).expect(LAYOUT)
+    }
+
+    /// Verifies a signature produced by [`sign_detached`] for the given `value`.
+    ///
+    /// [`sign_detached`]: Signer::sign_detached
+    fn verify_detached<'a, V>(
+        &'a self,
+        value: &'a V,
+        signature: &'a V,
+    ) -> Result<(), BadSignature<'a, V>>
+    where
+        V: ?Sized + AsRef<[u8]>,
+    {
+        if self.verify_encoded_signature(value.as_ref(), signature.as_ref()) {
+            Ok(())
+        } else {
+            Err(BadSignature::SignatureMismatch { signature, value })
+        }
+    }
+
+    fn separator(&self) -> &Separator;
 
     /// Given a base-64 encoded signature, attempt to verify whether or not
     /// it is valid for the given `value`.
@@ -55,24 +242,117 @@ pub trait Signer {
     fn signature_output_size(&self) -> usize;
 }
 
-pub trait GetSigner {
+/// A verifier can unsign values signed by a [`Signer`] that uses an asymmetric signing
+/// algorithm, using only the public key. Unlike a [`Signer`], it cannot sign values itself.
+///
+/// # Basic Usage
+/// ```rust
+/// # #[cfg(feature = "ed25519")] {
+/// use itsdangerous::{ed25519_builder, ed25519_verifier_builder, Signer, Verifier};
+///
+/// // The private key is only needed by the service that signs values...
+/// let signer = ed25519_builder([7; 32]).build().unwrap();
+/// let signed = signer.sign("hello world!");
+///
+/// // ...while other services are given a verifier built from the public key.
+/// let verifier = ed25519_verifier_builder(signer.public_key()).build().unwrap();
+/// assert_eq!(verifier.unsign(&signed).unwrap(), "hello world!");
+/// # }
+/// ```
+pub trait Verifier {
+    /// Unsigns the given string. The logical inverse of [`Signer::sign`].
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<&'a str, BadSignature<'a>> {
+        let (value, signature) = self.separator().split(value)?;
+        if self.verify_encoded_signature(value.as_bytes(), signature.as_bytes()) {
+            Ok(value)
+        } else {
+            Err(BadSignature::SignatureMismatch { signature, value })
+        }
+    }
+
+    /// Unsigns the given bytes. The logical inverse of [`Signer::sign_bytes`].
+    fn unsign_bytes<'a>(&'a self, value: &'a [u8]) -> Result<&'a [u8], BadSignature<'a, [u8]>> {
+        let (value, signature) = self.separator().split_bytes(value)?;
+        if self.verify_encoded_signature(value, signature) {
+            Ok(value)
+        } else {
+            Err(BadSignature::SignatureMismatch { signature, value })
+        }
+    }
+
+    /// Verifies a signature produced by [`Signer::sign_detached`] for the given `value`.
+    fn verify_detached<'a, V>(
+        &'a self,
+        value: &'a V,
+        signature: &'a V,
+    ) -> Result<(), BadSignature<'a, V>>
+    where
+        V: ?Sized + AsRef<[u8]>,
+    {
+        if self.verify_encoded_signature(value.as_ref(), signature.as_ref()) {
+            Ok(())
+        } else {
+            Err(BadSignature::SignatureMismatch { signature, value })
+        }
+    }
+
+    fn separator(&self) -> &Separator;
+
+    /// Given a base-64 encoded signature, attempt to verify whether or not
+    /// it is valid for the given `value`.
+    fn verify_encoded_signature(&self, value: &[u8], encoded_signature: &[u8]) -> bool;
+}
+
+/// A signer or verifier that knows the encoder of its signatures, which also determines how
+/// timestamps are encoded.
+pub trait GetEncoder {
+    type Encoder: SignatureEncoder;
+}
+
+pub trait GetSigner: GetEncoder {
     type OutputSize: ArrayLength<u8> + Unsigned;
     type Signer: AlgorithmSigner<OutputSize = Self::OutputSize>;
 
     /// Returns a signer that can be used to build a signature for a given key + values.
     fn get_signer(&self) -> Self::Signer;
 
+    /// Decodes `encoded_signature`, and checks it against a signer for the signing key and
+    /// each of the fallback keys in turn, after `input` has passed the signed value to it.
+    fn verify_signature_with<F>(&self, encoded_signature: &[u8], input: F) -> bool
+    where
+        F: FnMut(&mut Self::Signer);
+
     /// Returns the signature for a given key + value.
     fn get_signature(&self, value: &[u8]) -> Signature<Self::OutputSize> {
         self.get_signer().input_chained(value).sign()
     }
 }
 
+/// A signer that can sign and verify values that are provided in chunks, using
+/// a [`SignatureStream`].
+pub trait StreamingSigner {
+    type Signer: AlgorithmSigner;
+    type Encoder: SignatureEncoder;
+
+    /// Returns a [`SignatureStream`] that can be used to compute the signature of a value
+    /// using the signing key.
+    fn sign_stream(&self) -> SignatureStream<Self::Signer, Self::Encoder>;
+
+    /// Returns a [`SignatureStream`] that can be used to verify a signature of a value.
+    ///
+    /// Unlike [`sign_stream`], the returned stream also accepts signatures created with
+    /// any of the signer's fallback keys, at the cost of hashing each chunk once per key.
+    ///
+    /// [`sign_stream`]: StreamingSigner::sign_stream
+    fn verify_stream(&self) -> SignatureStream<Self::Signer, Self::Encoder>;
+}
+
 /// A TimestampSigner wraps an inner Signer, giving it the ability to dish
 /// out signatures with timestamps.
 ///
 /// # Basic Usage
 /// ```rust
+/// # #[cfg(feature = "std")] {
 /// use std::time::Duration;
 /// use itsdangerous::{default_builder, Signer, TimestampSigner, IntoTimestampSigner};
 ///
@@ -88,15 +368,112 @@ pub trait GetSigner {
 ///     .value_if_not_expired(Duration::from_secs(60))
 ///     .expect("Signature was expired");
 /// assert_eq!(value, "hello world!");
+/// # }
 /// ```
 pub trait TimestampSigner {
-    fn separator(&self) -> Separator;
+    fn separator(&self) -> &Separator;
+
+    /// The clock that the current time is taken from, when signing values without an
+    /// explicit timestamp, and when checking the age of unsigned values. Defaults to the
+    /// [`SystemClock`]. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn clock(&self) -> &dyn Clock {
+        &SystemClock
+    }
+
+    /// The policy that the timestamps of values are checked against when unsigning them, if
+    /// any. See [`ExpiryPolicy`]. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn expiry_policy(&self) -> Option<&ExpiryPolicy> {
+        None
+    }
 
     /// Signs a value with an arbitrary timestamp.
     fn sign_with_timestamp<S: AsRef<str>>(&self, value: S, timestamp: SystemTime) -> String;
 
-    /// Signs a value using the current system timestamp (as provided by [`SystemTime::now`]).
-    fn sign<S: AsRef<str>>(&self, value: S) -> String;
+    /// Signs a value with an arbitrary timestamp, appending the signed value to `target`.
+    /// See [`Signer::sign_into`].
+    fn sign_with_timestamp_into<S: AsRef<str>>(
+        &self,
+        value: S,
+        timestamp: SystemTime,
+        target: &mut String,
+    );
+
+    /// Signs a value with an arbitrary timestamp, writing the signed value to `target`
+    /// without allocating. See [`Signer::sign_to_fmt`].
+    fn sign_with_timestamp_to_fmt<S, W>(
+        &self,
+        value: S,
+        timestamp: SystemTime,
+        target: &mut W,
+    ) -> fmt::Result
+    where
+        S: AsRef<str>,
+        W: ?Sized + fmt::Write;
+
+    /// Signs the given bytes with an arbitrary timestamp into the start of `target` without
+    /// allocating, returning the length of the signed value. See [`Signer::sign_to_slice`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use itsdangerous::time::{Duration, UNIX_EPOCH};
+    /// use itsdangerous::{default_builder, IntoTimestampSigner, TimestampSigner};
+    ///
+    /// let signer = default_builder("secret key").build().into_timestamp_signer();
+    /// let timestamp = UNIX_EPOCH + Duration::from_secs(1560181622);
+    /// let mut buffer = [0; 64];
+    /// let length = signer
+    ///     .sign_with_timestamp_to_slice("hello", timestamp, &mut buffer)
+    ///     .unwrap();
+    /// let unsigned = signer.unsign_bytes(&buffer[..length]).unwrap();
+    /// assert_eq!(unsigned.value(), b"hello");
+    /// assert_eq!(unsigned.timestamp(), timestamp);
+    /// ```
+    fn sign_with_timestamp_to_slice<B: AsRef<[u8]>>(
+        &self,
+        value: B,
+        timestamp: SystemTime,
+        target: &mut [u8],
+    ) -> Result<usize, BufferTooSmall>;
+
+    /// Signs a value using the current time of the signer's [`Clock`], which is the system
+    /// time by default. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn sign<S: AsRef<str>>(&self, value: S) -> String {
+        self.sign_with_timestamp(value, self.clock().now())
+    }
+
+    /// Signs a value using the current time of the signer's [`Clock`], appending the signed
+    /// value to `target`. See [`Signer::sign_into`]. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn sign_into<S: AsRef<str>>(&self, value: S, target: &mut String) {
+        self.sign_with_timestamp_into(value, self.clock().now(), target)
+    }
+
+    /// Signs a value using the current time of the signer's [`Clock`], writing the signed
+    /// value to `target` without allocating. See [`Signer::sign_to_fmt`]. Requires the `std`
+    /// feature.
+    #[cfg(feature = "std")]
+    fn sign_to_fmt<S, W>(&self, value: S, target: &mut W) -> fmt::Result
+    where
+        S: AsRef<str>,
+        W: ?Sized + fmt::Write,
+    {
+        self.sign_with_timestamp_to_fmt(value, self.clock().now(), target)
+    }
+
+    /// Signs the given bytes using the current time of the signer's [`Clock`] into the start
+    /// of `target` without allocating. See [`Signer::sign_to_slice`]. Requires the `std`
+    /// feature.
+    #[cfg(feature = "std")]
+    fn sign_to_slice<B: AsRef<[u8]>>(
+        &self,
+        value: B,
+        target: &mut [u8],
+    ) -> Result<usize, BufferTooSmall> {
+        self.sign_with_timestamp_to_slice(value, self.clock().now(), target)
+    }
 
     /// The inverse of [`sign`] / [`sign_with_timestamp`], returning an [`UnsignedValue`], which you
     /// can grab the value, timestamp, and assert the max age of the signed value with.
@@ -110,7 +487,196 @@ pub trait TimestampSigner {
     /// [`&str`]: std::str
     /// [`sign`]: TimestampSigner::sign
     /// [`sign_with_timestamp`]: TimestampSigner::sign_with_timestamp
-    fn unsign<'a>(&'a self, value: &'a str) -> Result<UnsignedValue, BadTimedSignature<'a>>;
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<UnsignedValue<'a>, BadTimedSignature<'a>>;
+
+    /// Signs the given bytes with an arbitrary timestamp.
+    ///
+    /// This produces the same output as [`sign_with_timestamp`] if the bytes are valid utf-8.
+    ///
+    /// [`sign_with_timestamp`]: TimestampSigner::sign_with_timestamp
+    fn sign_bytes_with_timestamp<B: AsRef<[u8]>>(&self, value: B, timestamp: SystemTime)
+        -> Vec<u8>;
+
+    /// Signs the given bytes using the current time of the signer's [`Clock`]. Requires the
+    /// `std` feature.
+    #[cfg(feature = "std")]
+    fn sign_bytes<B: AsRef<[u8]>>(&self, value: B) -> Vec<u8> {
+        self.sign_bytes_with_timestamp(value, self.clock().now())
+    }
+
+    /// The inverse of [`sign_bytes`] / [`sign_bytes_with_timestamp`], returning an
+    /// [`UnsignedValue`] that borrows from the provided bytes.
+    ///
+    /// [`sign_bytes`]: TimestampSigner::sign_bytes
+    /// [`sign_bytes_with_timestamp`]: TimestampSigner::sign_bytes_with_timestamp
+    fn unsign_bytes<'a>(
+        &'a self,
+        value: &'a [u8],
+    ) -> Result<UnsignedValue<'a, [u8]>, BadTimedSignature<'a, [u8]>>;
+
+    /// Unsigns each of the given strings, returning the results in the same order. See
+    /// [`Signer::unsign_many`].
+    ///
+    /// Like [`unsign`], this enforces the signer's [`ExpiryPolicy`] for each value, if it has
+    /// one. Otherwise the age of each value is not checked, so you should still call
+    /// [`UnsignedValue::value_if_not_expired`] on each result.
+    ///
+    /// [`unsign`]: TimestampSigner::unsign
+    /// [`ExpiryPolicy`]: crate::ExpiryPolicy
+    fn unsign_many<'a, I>(
+        &'a self,
+        values: I,
+    ) -> Vec<Result<UnsignedValue<'a>, BadTimedSignature<'a>>>
+    where
+        Self: Sync,
+        I: IntoIterator<Item = &'a str>,
+    {
+        batch::map_in_order(values, |value| self.unsign(value))
+    }
+
+    /// Signs a value with an arbitrary timestamp, returning the encoded timestamp and
+    /// the encoded signature as `(timestamp, signature)`, rather than joining them
+    /// to the value.
+    ///
+    /// The default implementation takes the timestamp and signature from the output of
+    /// [`sign_bytes_with_timestamp`], which must be `{value}{sep}{timestamp}{sep}{signature}`.
+    ///
+    /// # Panics
+    /// Panics if the output of [`sign_bytes_with_timestamp`] does not have that layout, or if
+    /// the timestamp or signature in it are not valid utf-8.
+    ///
+    /// [`sign_bytes_with_timestamp`]: TimestampSigner::sign_bytes_with_timestamp
+    fn sign_detached_with_timestamp<B: AsRef<[u8]>>(
+        &self,
+        value: B,
+        timestamp: SystemTime,
+    ) -> (String, String) {
+        const LAYOUT: &str = "`sign_bytes_with_timestamp` must return \
+                              `{value}{sep}{timestamp}{sep}{signature}`";
+
+        let value = value.as_ref();
+        let signed = self.sign_bytes_with_timestamp(value, timestamp);
+        let separator = self.separator();
+        let (timestamp, signature) = signed
+            .get(value.len() + separator.as_bytes().len()..)
+            .and_then(|signed| separator.split_bytes(signed).ok())
+            .expect(LAYOUT);
+        (
+            String::from_utf8(timestamp.to_vec()).expect(LAYOUT),
+            String::from_utf8(signature.to_vec()).expect(LAYOUT),
+        )
+    }
+
+    /// Signs a value using the current time of the signer's [`Clock`], returning the encoded
+    /// timestamp and the encoded signature as `(timestamp, signature)`. Requires the
+    /// `std` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use itsdangerous::{default_builder, IntoTimestampSigner, TimestampSigner};
+    ///
+    /// let signer = default_builder("secret key").build().into_timestamp_signer();
+    /// let (timestamp, signature) = signer.sign_detached("hello world!");
+    /// let unsigned = signer
+    ///     .verify_detached("hello world!", timestamp.as_str(), signature.as_str())
+    ///     .expect("Signature was not valid");
+    /// assert!(unsigned.value_if_not_expired(Duration::from_secs(60)).is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    fn sign_detached<B: AsRef<[u8]>>(&self, value: B) -> (String, String) {
+        self.sign_detached_with_timestamp(value, self.clock().now())
+    }
+
+    /// Verifies a timestamp + signature produced by [`sign_detached`] or
+    /// [`sign_detached_with_timestamp`] for the given `value`, returning an
+    /// [`UnsignedValue`] which can be used to assert the max age of the signed value.
+    ///
+    /// [`sign_detached`]: TimestampSigner::sign_detached
+    /// [`sign_detached_with_timestamp`]: TimestampSigner::sign_detached_with_timestamp
+    fn verify_detached<'a, V>(
+        &'a self,
+        value: &'a V,
+        timestamp: &'a V,
+        signature: &'a V,
+    ) -> Result<UnsignedValue<'a, V>, BadTimedSignature<'a, V>>
+    where
+        V: ?Sized + AsRef<[u8]>;
+
+    /// Signs a value as a token which expires at `expires_at`, rather than with the time that
+    /// it was signed at. This lets the signer, rather than the unsigner, decide how long each
+    /// value is valid for. Use [`unsign_with_expiry`] to unsign it.
+    ///
+    /// The signed value is `{value}{sep}{sep}{expires_at}{sep}{signature}`, where `expires_at`
+    /// is encoded like the timestamp of [`sign_with_timestamp`]. The signature of a token is
+    /// computed differently from that of a value signed with a timestamp, so that neither can
+    /// be unsigned as the other.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// use itsdangerous::time::Duration;
+    /// use itsdangerous::{default_builder, IntoTimestampSigner, TimestampSigner};
+    ///
+    /// let signer = default_builder("secret key")
+    ///     .with_salt("tokens")
+    ///     .build()
+    ///     .into_timestamp_signer();
+    /// let now = signer.clock().now();
+    /// let session = signer.sign_with_expiry("session", now + Duration::from_secs(60 * 60));
+    /// let remember_me =
+    ///     signer.sign_with_expiry("remember me", now + Duration::from_secs(30 * 24 * 60 * 60));
+    ///
+    /// assert_eq!(signer.unsign_with_expiry(&session).unwrap().value(), "session");
+    /// assert_eq!(signer.unsign_with_expiry(&remember_me).unwrap().value(), "remember me");
+    /// # }
+    /// ```
+    ///
+    /// [`unsign_with_expiry`]: TimestampSigner::unsign_with_expiry
+    /// [`sign_with_timestamp`]: TimestampSigner::sign_with_timestamp
+    fn sign_with_expiry<S: AsRef<str>>(&self, value: S, expires_at: SystemTime) -> String;
+
+    /// Signs a value as a token which is valid from `not_before` until `expires_at`. See
+    /// [`sign_with_expiry`].
+    ///
+    /// [`sign_with_expiry`]: TimestampSigner::sign_with_expiry
+    fn sign_with_validity<S: AsRef<str>>(
+        &self,
+        value: S,
+        not_before: SystemTime,
+        expires_at: SystemTime,
+    ) -> String;
+
+    /// The inverse of [`sign_with_expiry`] / [`sign_with_validity`], returning an
+    /// [`ExpiringValue`].
+    ///
+    /// If `expires_at` has passed at the current time of the signer's [`Clock`], returns
+    /// [`BadTimedSignature::TokenExpired`], and if `not_before` has not, returns
+    /// [`BadTimedSignature::TokenNotYetValid`]. The [`ExpiryPolicy`] is not used. Requires the
+    /// `std` feature, see [`unsign_with_expiry_at`] otherwise.
+    ///
+    /// [`sign_with_expiry`]: TimestampSigner::sign_with_expiry
+    /// [`sign_with_validity`]: TimestampSigner::sign_with_validity
+    /// [`unsign_with_expiry_at`]: TimestampSigner::unsign_with_expiry_at
+    #[cfg(feature = "std")]
+    fn unsign_with_expiry<'a>(
+        &'a self,
+        value: &'a str,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>> {
+        self.unsign_with_expiry_at(value, self.clock().now())
+    }
+
+    /// The inverse of [`sign_with_expiry`] / [`sign_with_validity`], checking the token at
+    /// the time `now`, which is supplied by the caller. See [`unsign_with_expiry`].
+    ///
+    /// [`sign_with_expiry`]: TimestampSigner::sign_with_expiry
+    /// [`sign_with_validity`]: TimestampSigner::sign_with_validity
+    /// [`unsign_with_expiry`]: TimestampSigner::unsign_with_expiry
+    fn unsign_with_expiry_at<'a>(
+        &'a self,
+        value: &'a str,
+        now: SystemTime,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>>;
 }
 
 pub trait IntoTimestampSigner {
@@ -121,6 +687,68 @@ pub trait IntoTimestampSigner {
     fn into_timestamp_signer(self) -> Self::TimestampSigner;
 }
 
+/// A verify-only counterpart to [`TimestampSigner`], which can unsign values that were
+/// signed with a timestamp, but cannot sign values itself.
+pub trait TimestampVerifier {
+    fn separator(&self) -> &Separator;
+
+    /// The clock that the current time is taken from, when checking the age of unsigned
+    /// values. Defaults to the [`SystemClock`]. Requires the `std` feature.
+    #[cfg(feature = "std")]
+    fn clock(&self) -> &dyn Clock {
+        &SystemClock
+    }
+
+    /// See [`TimestampSigner::expiry_policy`].
+    #[cfg(feature = "std")]
+    fn expiry_policy(&self) -> Option<&ExpiryPolicy> {
+        None
+    }
+
+    /// See [`TimestampSigner::unsign`].
+    fn unsign<'a>(&'a self, value: &'a str) -> Result<UnsignedValue<'a>, BadTimedSignature<'a>>;
+
+    /// See [`TimestampSigner::unsign_bytes`].
+    fn unsign_bytes<'a>(
+        &'a self,
+        value: &'a [u8],
+    ) -> Result<UnsignedValue<'a, [u8]>, BadTimedSignature<'a, [u8]>>;
+
+    /// See [`TimestampSigner::verify_detached`].
+    fn verify_detached<'a, V>(
+        &'a self,
+        value: &'a V,
+        timestamp: &'a V,
+        signature: &'a V,
+    ) -> Result<UnsignedValue<'a, V>, BadTimedSignature<'a, V>>
+    where
+        V: ?Sized + AsRef<[u8]>;
+
+    /// See [`TimestampSigner::unsign_with_expiry`].
+    #[cfg(feature = "std")]
+    fn unsign_with_expiry<'a>(
+        &'a self,
+        value: &'a str,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>> {
+        self.unsign_with_expiry_at(value, self.clock().now())
+    }
+
+    /// See [`TimestampSigner::unsign_with_expiry_at`].
+    fn unsign_with_expiry_at<'a>(
+        &'a self,
+        value: &'a str,
+        now: SystemTime,
+    ) -> Result<ExpiringValue<'a>, BadTimedSignature<'a>>;
+}
+
+pub trait IntoTimestampVerifier {
+    type TimestampVerifier: TimestampVerifier;
+
+    /// Converts this [`Verifier`] into a [`TimestampVerifier`], giving it the ability
+    /// to unsign values that were signed with timestamps.
+    fn into_timestamp_verifier(self) -> Self::TimestampVerifier;
+}
+
 /// Returns a referenec to the underlying [`Signer`].
 pub trait AsSigner {
     type Signer: Signer;